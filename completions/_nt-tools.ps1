@@ -23,228 +23,525 @@ Register-ArgumentCompleter -Native -CommandName 'nt-tools' -ScriptBlock {
         'nt-tools' {
             [CompletionResult]::new('primes', 'primes', [CompletionResultType]::ParameterValue, 'Search for prime numbers between START and END numbers')
             [CompletionResult]::new('composites', 'composites', [CompletionResultType]::ParameterValue, 'Search for composite numbers between START and END numbers')
+            [CompletionResult]::new('next-prime', 'next-prime', [CompletionResultType]::ParameterValue, 'Smallest prime greater than or equal to NUM')
+            [CompletionResult]::new('prime-count', 'prime-count', [CompletionResultType]::ParameterValue, 'Prime counting function π(x) by the Lucy_Hedgehog method, 2 <= x <= 10¹⁴')
+            [CompletionResult]::new('nth-prime', 'nth-prime', [CompletionResultType]::ParameterValue, 'The nth prime pₙ, located with π(x) and the sieve')
+            [CompletionResult]::new('prime-gaps', 'prime-gaps', [CompletionResultType]::ParameterValue, 'Record and maximal gaps between consecutive primes between START and END numbers')
+            [CompletionResult]::new('prime-tuples', 'prime-tuples', [CompletionResultType]::ParameterValue, 'Prime constellations p, p + b₂, ..., p + bₖ with p between START and END numbers')
+            [CompletionResult]::new('arith-table', 'arith-table', [CompletionResultType]::ParameterValue, 'Table of φ, μ, σₖ, τ, λ, ω and Ω between START and END numbers factored in segments')
+            [CompletionResult]::new('goldbach', 'goldbach', [CompletionResultType]::ParameterValue, 'Goldbach partitions n = p + q of even NUM with their count, n = p + q + r for odd NUM, or a table of the partitions between START and END')
+            [CompletionResult]::new('squares', 'squares', [CompletionResultType]::ParameterValue, 'NUM as a sum of two squares (Cornacchia) and four squares (Rabin-Shallit) with r₂(n) and r₄(n), or a table between START and END')
             [CompletionResult]::new('composites-pq', 'composites-pq', [CompletionResultType]::ParameterValue, 'Search for composite numbers of the form "p.q" between START and END numbers')
             [CompletionResult]::new('nums-with-primitive-roots', 'nums-with-primitive-roots', [CompletionResultType]::ParameterValue, 'Search for numbers with primitive roots between START and END numbers')
             [CompletionResult]::new('carmichael-nums', 'carmichael-nums', [CompletionResultType]::ParameterValue, 'Carmichael Number search in a range.')
             [CompletionResult]::new('ifactors', 'ifactors', [CompletionResultType]::ParameterValue, 'Finds the Integer Factorisation of a number.')
             [CompletionResult]::new('primality', 'primality', [CompletionResultType]::ParameterValue, 'Primality checking capabilities.')
             [CompletionResult]::new('miller-rabin-liars', 'miller-rabin-liars', [CompletionResultType]::ParameterValue, 'List the Miller-Rabin Liars of a number if any exist')
+            [CompletionResult]::new('euler-jacobi-liars', 'euler-jacobi-liars', [CompletionResultType]::ParameterValue, 'List the Euler-Jacobi (Solovay-Strassen) Liars of a number if any exist')
             [CompletionResult]::new('gcd', 'gcd', [CompletionResultType]::ParameterValue, 'Finds the GCD of two numbers using Euclid''s algorithm.')
             [CompletionResult]::new('quadratic-sieve', 'quadratic-sieve', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Quadratic Sieve.')
-            [CompletionResult]::new('pollards-p-minus-1', 'pollards-p-minus-1', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Pollard''s P-1 Algm.')
+            [CompletionResult]::new('siqs', 'siqs', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Self-Initialising Quadratic Sieve')
+            [CompletionResult]::new('gf2-nullspace', 'gf2-nullspace', [CompletionResultType]::ParameterValue, 'Dependencies among the relations of a sieve over GF(2) by filtering and Block Lanczos')
+            [CompletionResult]::new('pollards-p-minus-1', 'pollards-p-minus-1', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Pollard''s P-1 Algm. with stage 2')
+            [CompletionResult]::new('williams-p-plus-1', 'williams-p-plus-1', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Williams'' P+1 Algm. with stage 2')
+            [CompletionResult]::new('pollards-rho-factor', 'pollards-rho-factor', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Pollard''s Rho Algm. with Brent''s cycle detection')
+            [CompletionResult]::new('fermat-factor', 'fermat-factor', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Fermat''s method, x² - kn = y²')
+            [CompletionResult]::new('lehman-factor', 'lehman-factor', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Lehman''s O(n¹ᐟ³) method, for n up to 64 bits')
+            [CompletionResult]::new('hart-factor', 'hart-factor', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Hart''s one line factoring, s = ⌈√(in)⌉ with s² mod n a square')
+            [CompletionResult]::new('euler-factor', 'euler-factor', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Euler''s method from two representations n = a² + b² = c² + d²')
+            [CompletionResult]::new('squfof', 'squfof', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Shanks'' square forms factorisation with multipliers, for n < 2⁶⁴')
+            [CompletionResult]::new('ecm', 'ecm', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Lenstra''s Elliptic Curve Method on Montgomery curves')
             [CompletionResult]::new('pollards-rho', 'pollards-rho', [CompletionResultType]::ParameterValue, 'Pollards Rho Alogorithm to find the logarithm modulo p')
             [CompletionResult]::new('modular-pow', 'modular-pow', [CompletionResultType]::ParameterValue, 'Fast Modular Exponentiation')
             [CompletionResult]::new('aks-findr', 'aks-findr', [CompletionResultType]::ParameterValue, 'Finds the ''r'' value for the AKS algorithm.')
             [CompletionResult]::new('list-primitive-roots', 'list-primitive-roots', [CompletionResultType]::ParameterValue, 'List the primitive roots of a number')
             [CompletionResult]::new('ass2q2b', 'ass2q2b', [CompletionResultType]::ParameterValue, 'Assignment 2 - Question 2b - Primitive Roots - Euler''s Totient Function')
             [CompletionResult]::new('ass2q2c', 'ass2q2c', [CompletionResultType]::ParameterValue, 'Assignment 2 - Question 2c - pᵏ(p > 2, k ≥ 1) or 2pᵏ(p > 2, k ≥ 1)')
-            [CompletionResult]::new('ass2q3d', 'ass2q3d', [CompletionResultType]::ParameterValue, 'Assignment 2 - Question 3d - all numbers of the form n = pq (p and q both odd primes) in your range are included
-in set B')
-            [CompletionResult]::new('aks-failed-steps-for-n', 'aks-failed-steps-for-n', [CompletionResultType]::ParameterValue, 'Assignment 1 - Question 4c - Choose any three elements of your set A and calculate the value of r used in the AKS primality
-test')
+            [CompletionResult]::new('ass2q3d', 'ass2q3d', [CompletionResultType]::ParameterValue, 'Assignment 2 - Question 3d - all numbers of the form n = pq (p and q both odd primes) in your range are included in set B')
+            [CompletionResult]::new('aks-failed-steps-for-n', 'aks-failed-steps-for-n', [CompletionResultType]::ParameterValue, 'Assignment 1 - Question 4c - Choose any three elements of your set A and calculate the value of r used in the AKS primality test')
+            [CompletionResult]::new('mersenne', 'mersenne', [CompletionResultType]::ParameterValue, 'Lucas-Lehmer test of the Mersenne numbers 2ᵖ - 1 for the prime exponents p between START and END')
+            [CompletionResult]::new('pepin', 'pepin', [CompletionResultType]::ParameterValue, 'Pépin''s test of the Fermat numbers 2^(2ᵐ) + 1 for START <= m <= END <= 16')
+            [CompletionResult]::new('proth', 'proth', [CompletionResultType]::ParameterValue, 'Proth''s test of N = k.2ⁿ + 1 with a certificate of primality')
+            [CompletionResult]::new('gen-prime', 'gen-prime', [CompletionResultType]::ParameterValue, 'Generate random primes of a given bit length')
+            [CompletionResult]::new('certify', 'certify', [CompletionResultType]::ParameterValue, 'Generate a Pratt or Pocklington-Lehmer certificate of primality as JSON')
+            [CompletionResult]::new('verify-cert', 'verify-cert', [CompletionResultType]::ParameterValue, 'Independently check a certificate of primality')
+            [CompletionResult]::new('seed', 'seed', [CompletionResultType]::ParameterValue, 'Set or show the seed of the random number generator for reproducible results.')
             [CompletionResult]::new('clear', 'clear', [CompletionResultType]::ParameterValue, 'clear')
             [CompletionResult]::new('quit', 'quit', [CompletionResultType]::ParameterValue, 'quit')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
         }
         'nt-tools;primes' {
-            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
-            [CompletionResult]::new('--start', 'start', [CompletionResultType]::ParameterName, 'start')
-            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
-            [CompletionResult]::new('--end', 'end', [CompletionResultType]::ParameterName, 'end')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;composites' {
-            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
-            [CompletionResult]::new('--start', 'start', [CompletionResultType]::ParameterName, 'start')
-            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
-            [CompletionResult]::new('--end', 'end', [CompletionResultType]::ParameterName, 'end')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;next-prime' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;prime-count' {
+            [CompletionResult]::new('-x', '-x', [CompletionResultType]::ParameterName, 'One or more values of x, comma or space separated')
+            [CompletionResult]::new('--x', '--x', [CompletionResultType]::ParameterName, 'One or more values of x, comma or space separated')
+            [CompletionResult]::new('--estimates', '--estimates', [CompletionResultType]::ParameterName, 'Add the columns x/ln x, li(x) and their relative errors')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;nth-prime' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'One or more indices n, comma or space separated')
+            [CompletionResult]::new('--n', '--n', [CompletionResultType]::ParameterName, 'One or more indices n, comma or space separated')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;prime-gaps' {
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('--histogram', '--histogram', [CompletionResultType]::ParameterName, 'Also print how often each gap occurs')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;prime-tuples' {
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-p', '-p', [CompletionResultType]::ParameterName, 'twins, cousins, sexy or admissible offsets such as 0,2,6,8')
+            [CompletionResult]::new('--pattern', '--pattern', [CompletionResultType]::ParameterName, 'twins, cousins, sexy or admissible offsets such as 0,2,6,8')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;arith-table' {
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Columns of the table, comma or space separated')
+            [CompletionResult]::new('--functions', '--functions', [CompletionResultType]::ParameterName, 'Columns of the table, comma or space separated')
+            [CompletionResult]::new('-k', '-k', [CompletionResultType]::ParameterName, 'Power k of the divisor function σₖ(n)')
+            [CompletionResult]::new('--k', '--k', [CompletionResultType]::ParameterName, 'Power k of the divisor function σₖ(n)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;goldbach' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('--seed', '--seed', [CompletionResultType]::ParameterName, 'Seed for Miller-Rabin above 3.3·10²⁴, overrides the session seed')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;squares' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('--seed', '--seed', [CompletionResultType]::ParameterName, 'Seed for the random squares of Rabin-Shallit, overrides the session seed')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;composites-pq' {
-            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
-            [CompletionResult]::new('--start', 'start', [CompletionResultType]::ParameterName, 'start')
-            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
-            [CompletionResult]::new('--end', 'end', [CompletionResultType]::ParameterName, 'end')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;nums-with-primitive-roots' {
-            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
-            [CompletionResult]::new('--start', 'start', [CompletionResultType]::ParameterName, 'start')
-            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
-            [CompletionResult]::new('--end', 'end', [CompletionResultType]::ParameterName, 'end')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;carmichael-nums' {
-            [CompletionResult]::new('--method', 'method', [CompletionResultType]::ParameterName, 'Choose the algorithm')
-            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
-            [CompletionResult]::new('--start', 'start', [CompletionResultType]::ParameterName, 'start')
-            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
-            [CompletionResult]::new('--end', 'end', [CompletionResultType]::ParameterName, 'end')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--method', '--method', [CompletionResultType]::ParameterName, 'Choose the algorithm')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;ifactors' {
-            [CompletionResult]::new('-a', 'a', [CompletionResultType]::ParameterName, 'a')
-            [CompletionResult]::new('--num1', 'num1', [CompletionResultType]::ParameterName, 'num1')
-            [CompletionResult]::new('-b', 'b', [CompletionResultType]::ParameterName, 'b')
-            [CompletionResult]::new('--num2', 'num2', [CompletionResultType]::ParameterName, 'num2')
-            [CompletionResult]::new('--pq', 'pq', [CompletionResultType]::ParameterName, 'pq')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'a')
+            [CompletionResult]::new('--num1', '--num1', [CompletionResultType]::ParameterName, 'num1')
+            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'b')
+            [CompletionResult]::new('--num2', '--num2', [CompletionResultType]::ParameterName, 'num2')
+            [CompletionResult]::new('--seed', '--seed', [CompletionResultType]::ParameterName, 'Seed for Pollard''s rho on large cofactors, overrides the session seed')
+            [CompletionResult]::new('--pq', '--pq', [CompletionResultType]::ParameterName, 'pq')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;primality' {
-            [CompletionResult]::new('--method', 'method', [CompletionResultType]::ParameterName, 'Choose the primality Checking algorithm')
-            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'n')
-            [CompletionResult]::new('--num', 'num', [CompletionResultType]::ParameterName, 'num')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--method', '--method', [CompletionResultType]::ParameterName, 'Choose the primality Checking algorithm')
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'Comma separated list of bases to test (fermat, miller-rabin, solovay-strassen)')
+            [CompletionResult]::new('--bases', '--bases', [CompletionResultType]::ParameterName, 'Comma separated list of bases to test (fermat, miller-rabin, solovay-strassen)')
+            [CompletionResult]::new('-r', '-r', [CompletionResultType]::ParameterName, 'Number of random bases to try when no bases are passed (miller-rabin: only above 3.3·10²⁴)')
+            [CompletionResult]::new('--rounds', '--rounds', [CompletionResultType]::ParameterName, 'Number of random bases to try when no bases are passed (miller-rabin: only above 3.3·10²⁴)')
+            [CompletionResult]::new('--seed', '--seed', [CompletionResultType]::ParameterName, 'Seed for the random bases, overrides the session seed')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;miller-rabin-liars' {
-            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'n')
-            [CompletionResult]::new('--num', 'num', [CompletionResultType]::ParameterName, 'num')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;euler-jacobi-liars' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;gcd' {
-            [CompletionResult]::new('-a', 'a', [CompletionResultType]::ParameterName, 'a')
-            [CompletionResult]::new('--num1', 'num1', [CompletionResultType]::ParameterName, 'num1')
-            [CompletionResult]::new('-b', 'b', [CompletionResultType]::ParameterName, 'b')
-            [CompletionResult]::new('--num2', 'num2', [CompletionResultType]::ParameterName, 'num2')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'a')
+            [CompletionResult]::new('--num1', '--num1', [CompletionResultType]::ParameterName, 'num1')
+            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'b')
+            [CompletionResult]::new('--num2', '--num2', [CompletionResultType]::ParameterName, 'num2')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;quadratic-sieve' {
-            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'n')
-            [CompletionResult]::new('--num', 'num', [CompletionResultType]::ParameterName, 'num')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'Smoothness bound B of the factor base, at least 30, defaults to L(n)¹ᐟ²')
+            [CompletionResult]::new('--bound', '--bound', [CompletionResultType]::ParameterName, 'Smoothness bound B of the factor base, at least 30, defaults to L(n)¹ᐟ²')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;siqs' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('--fb-size', '--fb-size', [CompletionResultType]::ParameterName, 'Number of primes in the factor base, at least 60, defaults to a table by the size of n')
+            [CompletionResult]::new('-c', '-c', [CompletionResultType]::ParameterName, 'File the relations are appended to, a run with the same file resumes from them')
+            [CompletionResult]::new('--checkpoint', '--checkpoint', [CompletionResultType]::ParameterName, 'File the relations are appended to, a run with the same file resumes from them')
+            [CompletionResult]::new('--seed', '--seed', [CompletionResultType]::ParameterName, 'Seed for the choice of the A coefficients, overrides the session seed')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;gf2-nullspace' {
+            [CompletionResult]::new('-o', '-o', [CompletionResultType]::ParameterName, 'Write the dependencies to a file, the line numbers of each on a line')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Write the dependencies to a file, the line numbers of each on a line')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;pollards-p-minus-1' {
-            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'n')
-            [CompletionResult]::new('--num', 'num', [CompletionResultType]::ParameterName, 'num')
-            [CompletionResult]::new('-b', 'b', [CompletionResultType]::ParameterName, 'b')
-            [CompletionResult]::new('--base', 'base', [CompletionResultType]::ParameterName, 'base')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'b')
+            [CompletionResult]::new('--base', '--base', [CompletionResultType]::ParameterName, 'base')
+            [CompletionResult]::new('--b1', '--b1', [CompletionResultType]::ParameterName, 'Stage 1 bound B1, defaults to n¹ᐟ⁶ within [10³, 10⁶]')
+            [CompletionResult]::new('--b2', '--b2', [CompletionResultType]::ParameterName, 'Stage 2 bound B2, defaults to 100.B1')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;williams-p-plus-1' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'Starting value V₁ = A of the Lucas sequence, A + 1 and A + 2 are tried next')
+            [CompletionResult]::new('--a', '--a', [CompletionResultType]::ParameterName, 'Starting value V₁ = A of the Lucas sequence, A + 1 and A + 2 are tried next')
+            [CompletionResult]::new('--b1', '--b1', [CompletionResultType]::ParameterName, 'Stage 1 bound B1, defaults to n¹ᐟ⁶ within [10³, 10⁶]')
+            [CompletionResult]::new('--b2', '--b2', [CompletionResultType]::ParameterName, 'Stage 2 bound B2, defaults to 100.B1')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;pollards-rho-factor' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('--seed', '--seed', [CompletionResultType]::ParameterName, 'Seed for the random c and starting point, overrides the session seed')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;fermat-factor' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-k', '-k', [CompletionResultType]::ParameterName, 'Run on kn, for factors p, q with q/p close to k')
+            [CompletionResult]::new('--multiplier', '--multiplier', [CompletionResultType]::ParameterName, 'Run on kn, for factors p, q with q/p close to k')
+            [CompletionResult]::new('--max-steps', '--max-steps', [CompletionResultType]::ParameterName, 'Values of x tried before giving up')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;lehman-factor' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;hart-factor' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('--max-steps', '--max-steps', [CompletionResultType]::ParameterName, 'Multipliers i tried before giving up')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;euler-factor' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('--seed', '--seed', [CompletionResultType]::ParameterName, 'Seed for the factorisation of n above 10¹², overrides the session seed')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;squfof' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;ecm' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('--b1', '--b1', [CompletionResultType]::ParameterName, 'Stage 1 bound B1, 11000 suits factors of about 20 digits')
+            [CompletionResult]::new('--b2', '--b2', [CompletionResultType]::ParameterName, 'Stage 2 bound B2, defaults to 100.B1')
+            [CompletionResult]::new('-c', '-c', [CompletionResultType]::ParameterName, 'Number of curves to try, run in parallel')
+            [CompletionResult]::new('--curves', '--curves', [CompletionResultType]::ParameterName, 'Number of curves to try, run in parallel')
+            [CompletionResult]::new('--seed', '--seed', [CompletionResultType]::ParameterName, 'Seed for the random curve parameters σ, overrides the session seed')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;pollards-rho' {
-            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'Primitive Root modulo N')
-            [CompletionResult]::new('--primitive-root', 'primitive-root', [CompletionResultType]::ParameterName, 'Primitive Root modulo N')
-            [CompletionResult]::new('-b', 'b', [CompletionResultType]::ParameterName, 'b ∈ Z/pZ - Find the logarithm of b to the base r')
-            [CompletionResult]::new('-m', 'm', [CompletionResultType]::ParameterName, 'Odd Prime Number')
-            [CompletionResult]::new('--modulo', 'modulo', [CompletionResultType]::ParameterName, 'Odd Prime Number')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-r', '-r', [CompletionResultType]::ParameterName, 'Primitive Root modulo N')
+            [CompletionResult]::new('--primitive-root', '--primitive-root', [CompletionResultType]::ParameterName, 'Primitive Root modulo N')
+            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'b ∈ Z/pZ - Find the logarithm of b to the base r')
+            [CompletionResult]::new('-m', '-m', [CompletionResultType]::ParameterName, 'Odd Prime Number')
+            [CompletionResult]::new('--modulo', '--modulo', [CompletionResultType]::ParameterName, 'Odd Prime Number')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;modular-pow' {
-            [CompletionResult]::new('-b', 'b', [CompletionResultType]::ParameterName, 'Base number which we are raising to some power')
-            [CompletionResult]::new('--base', 'base', [CompletionResultType]::ParameterName, 'Base number which we are raising to some power')
-            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'Exponent')
-            [CompletionResult]::new('-m', 'm', [CompletionResultType]::ParameterName, 'Modulus')
-            [CompletionResult]::new('--modulo', 'modulo', [CompletionResultType]::ParameterName, 'Modulus')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'Base number which we are raising to some power')
+            [CompletionResult]::new('--base', '--base', [CompletionResultType]::ParameterName, 'Base number which we are raising to some power')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'Exponent')
+            [CompletionResult]::new('-m', '-m', [CompletionResultType]::ParameterName, 'Modulus')
+            [CompletionResult]::new('--modulo', '--modulo', [CompletionResultType]::ParameterName, 'Modulus')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;aks-findr' {
-            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'n')
-            [CompletionResult]::new('--num', 'num', [CompletionResultType]::ParameterName, 'num')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;list-primitive-roots' {
-            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'n')
-            [CompletionResult]::new('--num', 'num', [CompletionResultType]::ParameterName, 'num')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;ass2q2b' {
-            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
-            [CompletionResult]::new('--start', 'start', [CompletionResultType]::ParameterName, 'start')
-            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
-            [CompletionResult]::new('--end', 'end', [CompletionResultType]::ParameterName, 'end')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;ass2q2c' {
-            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
-            [CompletionResult]::new('--start', 'start', [CompletionResultType]::ParameterName, 'start')
-            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
-            [CompletionResult]::new('--end', 'end', [CompletionResultType]::ParameterName, 'end')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;ass2q3d' {
-            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
-            [CompletionResult]::new('--start', 'start', [CompletionResultType]::ParameterName, 'start')
-            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
-            [CompletionResult]::new('--end', 'end', [CompletionResultType]::ParameterName, 'end')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;aks-failed-steps-for-n' {
-            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
-            [CompletionResult]::new('--start', 'start', [CompletionResultType]::ParameterName, 'start')
-            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
-            [CompletionResult]::new('--end', 'end', [CompletionResultType]::ParameterName, 'end')
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;mersenne' {
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('--k-max', '--k-max', [CompletionResultType]::ParameterName, 'Largest k tried when searching for factors 2kp + 1')
+            [CompletionResult]::new('--factors', '--factors', [CompletionResultType]::ParameterName, 'Search composite Mₚ for a small factor of the form 2kp + 1')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;pepin' {
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--start', '--start', [CompletionResultType]::ParameterName, 'start')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'e')
+            [CompletionResult]::new('--end', '--end', [CompletionResultType]::ParameterName, 'end')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;proth' {
+            [CompletionResult]::new('-k', '-k', [CompletionResultType]::ParameterName, 'Odd multiplier k < 2ⁿ')
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'Power of 2, at most 65536')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;gen-prime' {
+            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'b')
+            [CompletionResult]::new('--bits', '--bits', [CompletionResultType]::ParameterName, 'bits')
+            [CompletionResult]::new('-c', '-c', [CompletionResultType]::ParameterName, 'Number of primes to generate')
+            [CompletionResult]::new('--count', '--count', [CompletionResultType]::ParameterName, 'Number of primes to generate')
+            [CompletionResult]::new('--seed', '--seed', [CompletionResultType]::ParameterName, 'Seed for the random starting points, overrides the session seed')
+            [CompletionResult]::new('--safe', '--safe', [CompletionResultType]::ParameterName, 'Safe prime p, (p - 1)/2 is prime')
+            [CompletionResult]::new('--strong', '--strong', [CompletionResultType]::ParameterName, 'Strong prime by Gordon''s algorithm, at least 64 bits')
+            [CompletionResult]::new('--sophie-germain', '--sophie-germain', [CompletionResultType]::ParameterName, 'Sophie Germain prime p, 2p + 1 is prime')
+            [CompletionResult]::new('--cert', '--cert', [CompletionResultType]::ParameterName, 'Construct provable primes and print a Pocklington certificate for each')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;certify' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--num', '--num', [CompletionResultType]::ParameterName, 'num')
+            [CompletionResult]::new('-m', '-m', [CompletionResultType]::ParameterName, 'm')
+            [CompletionResult]::new('--method', '--method', [CompletionResultType]::ParameterName, 'method')
+            [CompletionResult]::new('-o', '-o', [CompletionResultType]::ParameterName, 'Write the certificate to a file instead of printing it')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Write the certificate to a file instead of printing it')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;verify-cert' {
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'nt-tools;seed' {
+            [CompletionResult]::new('--clear', '--clear', [CompletionResultType]::ParameterName, 'Draw a fresh seed for every command')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;clear' {
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;quit' {
-            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'nt-tools;help' {
             [CompletionResult]::new('primes', 'primes', [CompletionResultType]::ParameterValue, 'Search for prime numbers between START and END numbers')
             [CompletionResult]::new('composites', 'composites', [CompletionResultType]::ParameterValue, 'Search for composite numbers between START and END numbers')
+            [CompletionResult]::new('next-prime', 'next-prime', [CompletionResultType]::ParameterValue, 'Smallest prime greater than or equal to NUM')
+            [CompletionResult]::new('prime-count', 'prime-count', [CompletionResultType]::ParameterValue, 'Prime counting function π(x) by the Lucy_Hedgehog method, 2 <= x <= 10¹⁴')
+            [CompletionResult]::new('nth-prime', 'nth-prime', [CompletionResultType]::ParameterValue, 'The nth prime pₙ, located with π(x) and the sieve')
+            [CompletionResult]::new('prime-gaps', 'prime-gaps', [CompletionResultType]::ParameterValue, 'Record and maximal gaps between consecutive primes between START and END numbers')
+            [CompletionResult]::new('prime-tuples', 'prime-tuples', [CompletionResultType]::ParameterValue, 'Prime constellations p, p + b₂, ..., p + bₖ with p between START and END numbers')
+            [CompletionResult]::new('arith-table', 'arith-table', [CompletionResultType]::ParameterValue, 'Table of φ, μ, σₖ, τ, λ, ω and Ω between START and END numbers factored in segments')
+            [CompletionResult]::new('goldbach', 'goldbach', [CompletionResultType]::ParameterValue, 'Goldbach partitions n = p + q of even NUM with their count, n = p + q + r for odd NUM, or a table of the partitions between START and END')
+            [CompletionResult]::new('squares', 'squares', [CompletionResultType]::ParameterValue, 'NUM as a sum of two squares (Cornacchia) and four squares (Rabin-Shallit) with r₂(n) and r₄(n), or a table between START and END')
             [CompletionResult]::new('composites-pq', 'composites-pq', [CompletionResultType]::ParameterValue, 'Search for composite numbers of the form "p.q" between START and END numbers')
             [CompletionResult]::new('nums-with-primitive-roots', 'nums-with-primitive-roots', [CompletionResultType]::ParameterValue, 'Search for numbers with primitive roots between START and END numbers')
             [CompletionResult]::new('carmichael-nums', 'carmichael-nums', [CompletionResultType]::ParameterValue, 'Carmichael Number search in a range.')
             [CompletionResult]::new('ifactors', 'ifactors', [CompletionResultType]::ParameterValue, 'Finds the Integer Factorisation of a number.')
             [CompletionResult]::new('primality', 'primality', [CompletionResultType]::ParameterValue, 'Primality checking capabilities.')
             [CompletionResult]::new('miller-rabin-liars', 'miller-rabin-liars', [CompletionResultType]::ParameterValue, 'List the Miller-Rabin Liars of a number if any exist')
+            [CompletionResult]::new('euler-jacobi-liars', 'euler-jacobi-liars', [CompletionResultType]::ParameterValue, 'List the Euler-Jacobi (Solovay-Strassen) Liars of a number if any exist')
             [CompletionResult]::new('gcd', 'gcd', [CompletionResultType]::ParameterValue, 'Finds the GCD of two numbers using Euclid''s algorithm.')
             [CompletionResult]::new('quadratic-sieve', 'quadratic-sieve', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Quadratic Sieve.')
-            [CompletionResult]::new('pollards-p-minus-1', 'pollards-p-minus-1', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Pollard''s P-1 Algm.')
+            [CompletionResult]::new('siqs', 'siqs', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Self-Initialising Quadratic Sieve')
+            [CompletionResult]::new('gf2-nullspace', 'gf2-nullspace', [CompletionResultType]::ParameterValue, 'Dependencies among the relations of a sieve over GF(2) by filtering and Block Lanczos')
+            [CompletionResult]::new('pollards-p-minus-1', 'pollards-p-minus-1', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Pollard''s P-1 Algm. with stage 2')
+            [CompletionResult]::new('williams-p-plus-1', 'williams-p-plus-1', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Williams'' P+1 Algm. with stage 2')
+            [CompletionResult]::new('pollards-rho-factor', 'pollards-rho-factor', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Pollard''s Rho Algm. with Brent''s cycle detection')
+            [CompletionResult]::new('fermat-factor', 'fermat-factor', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Fermat''s method, x² - kn = y²')
+            [CompletionResult]::new('lehman-factor', 'lehman-factor', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Lehman''s O(n¹ᐟ³) method, for n up to 64 bits')
+            [CompletionResult]::new('hart-factor', 'hart-factor', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Hart''s one line factoring, s = ⌈√(in)⌉ with s² mod n a square')
+            [CompletionResult]::new('euler-factor', 'euler-factor', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Euler''s method from two representations n = a² + b² = c² + d²')
+            [CompletionResult]::new('squfof', 'squfof', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Shanks'' square forms factorisation with multipliers, for n < 2⁶⁴')
+            [CompletionResult]::new('ecm', 'ecm', [CompletionResultType]::ParameterValue, 'Integer Factorisation - Lenstra''s Elliptic Curve Method on Montgomery curves')
             [CompletionResult]::new('pollards-rho', 'pollards-rho', [CompletionResultType]::ParameterValue, 'Pollards Rho Alogorithm to find the logarithm modulo p')
             [CompletionResult]::new('modular-pow', 'modular-pow', [CompletionResultType]::ParameterValue, 'Fast Modular Exponentiation')
             [CompletionResult]::new('aks-findr', 'aks-findr', [CompletionResultType]::ParameterValue, 'Finds the ''r'' value for the AKS algorithm.')
             [CompletionResult]::new('list-primitive-roots', 'list-primitive-roots', [CompletionResultType]::ParameterValue, 'List the primitive roots of a number')
             [CompletionResult]::new('ass2q2b', 'ass2q2b', [CompletionResultType]::ParameterValue, 'Assignment 2 - Question 2b - Primitive Roots - Euler''s Totient Function')
             [CompletionResult]::new('ass2q2c', 'ass2q2c', [CompletionResultType]::ParameterValue, 'Assignment 2 - Question 2c - pᵏ(p > 2, k ≥ 1) or 2pᵏ(p > 2, k ≥ 1)')
-            [CompletionResult]::new('ass2q3d', 'ass2q3d', [CompletionResultType]::ParameterValue, 'Assignment 2 - Question 3d - all numbers of the form n = pq (p and q both odd primes) in your range are included
-in set B')
-            [CompletionResult]::new('aks-failed-steps-for-n', 'aks-failed-steps-for-n', [CompletionResultType]::ParameterValue, 'Assignment 1 - Question 4c - Choose any three elements of your set A and calculate the value of r used in the AKS primality
-test')
+            [CompletionResult]::new('ass2q3d', 'ass2q3d', [CompletionResultType]::ParameterValue, 'Assignment 2 - Question 3d - all numbers of the form n = pq (p and q both odd primes) in your range are included in set B')
+            [CompletionResult]::new('aks-failed-steps-for-n', 'aks-failed-steps-for-n', [CompletionResultType]::ParameterValue, 'Assignment 1 - Question 4c - Choose any three elements of your set A and calculate the value of r used in the AKS primality test')
+            [CompletionResult]::new('mersenne', 'mersenne', [CompletionResultType]::ParameterValue, 'Lucas-Lehmer test of the Mersenne numbers 2ᵖ - 1 for the prime exponents p between START and END')
+            [CompletionResult]::new('pepin', 'pepin', [CompletionResultType]::ParameterValue, 'Pépin''s test of the Fermat numbers 2^(2ᵐ) + 1 for START <= m <= END <= 16')
+            [CompletionResult]::new('proth', 'proth', [CompletionResultType]::ParameterValue, 'Proth''s test of N = k.2ⁿ + 1 with a certificate of primality')
+            [CompletionResult]::new('gen-prime', 'gen-prime', [CompletionResultType]::ParameterValue, 'Generate random primes of a given bit length')
+            [CompletionResult]::new('certify', 'certify', [CompletionResultType]::ParameterValue, 'Generate a Pratt or Pocklington-Lehmer certificate of primality as JSON')
+            [CompletionResult]::new('verify-cert', 'verify-cert', [CompletionResultType]::ParameterValue, 'Independently check a certificate of primality')
+            [CompletionResult]::new('seed', 'seed', [CompletionResultType]::ParameterValue, 'Set or show the seed of the random number generator for reproducible results.')
             [CompletionResult]::new('clear', 'clear', [CompletionResultType]::ParameterValue, 'clear')
             [CompletionResult]::new('quit', 'quit', [CompletionResultType]::ParameterValue, 'quit')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
@@ -256,6 +553,30 @@ test')
         'nt-tools;help;composites' {
             break
         }
+        'nt-tools;help;next-prime' {
+            break
+        }
+        'nt-tools;help;prime-count' {
+            break
+        }
+        'nt-tools;help;nth-prime' {
+            break
+        }
+        'nt-tools;help;prime-gaps' {
+            break
+        }
+        'nt-tools;help;prime-tuples' {
+            break
+        }
+        'nt-tools;help;arith-table' {
+            break
+        }
+        'nt-tools;help;goldbach' {
+            break
+        }
+        'nt-tools;help;squares' {
+            break
+        }
         'nt-tools;help;composites-pq' {
             break
         }
@@ -274,15 +595,48 @@ test')
         'nt-tools;help;miller-rabin-liars' {
             break
         }
+        'nt-tools;help;euler-jacobi-liars' {
+            break
+        }
         'nt-tools;help;gcd' {
             break
         }
         'nt-tools;help;quadratic-sieve' {
             break
         }
+        'nt-tools;help;siqs' {
+            break
+        }
+        'nt-tools;help;gf2-nullspace' {
+            break
+        }
         'nt-tools;help;pollards-p-minus-1' {
             break
         }
+        'nt-tools;help;williams-p-plus-1' {
+            break
+        }
+        'nt-tools;help;pollards-rho-factor' {
+            break
+        }
+        'nt-tools;help;fermat-factor' {
+            break
+        }
+        'nt-tools;help;lehman-factor' {
+            break
+        }
+        'nt-tools;help;hart-factor' {
+            break
+        }
+        'nt-tools;help;euler-factor' {
+            break
+        }
+        'nt-tools;help;squfof' {
+            break
+        }
+        'nt-tools;help;ecm' {
+            break
+        }
         'nt-tools;help;pollards-rho' {
             break
         }
@@ -307,6 +661,27 @@ test')
         'nt-tools;help;aks-failed-steps-for-n' {
             break
         }
+        'nt-tools;help;mersenne' {
+            break
+        }
+        'nt-tools;help;pepin' {
+            break
+        }
+        'nt-tools;help;proth' {
+            break
+        }
+        'nt-tools;help;gen-prime' {
+            break
+        }
+        'nt-tools;help;certify' {
+            break
+        }
+        'nt-tools;help;verify-cert' {
+            break
+        }
+        'nt-tools;help;seed' {
+            break
+        }
         'nt-tools;help;clear' {
             break
         }
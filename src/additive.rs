@@ -0,0 +1,530 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+use rand::rngs::StdRng;
+
+use crate::{
+    arithmetic_functions::factor_range,
+    display::EulerFactorTable,
+    factorisations::factorise,
+    primality::miller_rabin_primality,
+    sieve::PrimeStream,
+    utils::{generate_random_int_in_range, sqrt_mod_prime},
+};
+
+/// Goldbach partitions are counted up to this n, one primality test per prime p <= n/2.
+const GOLDBACH_COUNT_LIMIT: u64 = 100_000_000;
+/// Up to this n the representations n = a² + b² are searched for, about 0.3√n square roots.
+const TWO_SQUARES_SEARCH_LIMIT: u64 = 1_000_000_000_000;
+
+///
+/// The Goldbach partition n = p + q with the least prime p, for even n >= 4
+///
+pub fn goldbach_minimal(n: &BigInt, rng: &mut StdRng) -> Result<(BigInt, BigInt), String> {
+    if n.is_odd() || n < &BigInt::from(4u64) {
+        return Err(format!(
+            "error: {} has no Goldbach partition, n must be even and >= 4\n",
+            n
+        ));
+    }
+    let mut p = BigInt::from(2u64);
+    loop {
+        let q = n - &p;
+        if miller_rabin_primality(&p, rng) && miller_rabin_primality(&q, rng) {
+            return Ok((p, q));
+        }
+        p += if p == BigInt::from(2u64) { 1u32 } else { 2u32 };
+    }
+}
+
+///
+/// The number of Goldbach partitions n = p + q with p <= q. The primes p <= n/2 are
+/// streamed from the sieve and n - p is tested with Miller-Rabin.
+///
+pub fn goldbach_count(n: u64, rng: &mut StdRng) -> Result<u64, String> {
+    if n % 2 == 1 || n < 4 {
+        return Err(format!(
+            "error: {} has no Goldbach partition, n must be even and >= 4\n",
+            n
+        ));
+    }
+    if n > GOLDBACH_COUNT_LIMIT {
+        return Err(format!(
+            "error: partitions are only counted up to {}\n",
+            GOLDBACH_COUNT_LIMIT
+        ));
+    }
+    Ok(PrimeStream::new(2, n / 2)
+        .filter(|p| miller_rabin_primality(&BigInt::from(n - p), rng))
+        .count() as u64)
+}
+
+///
+/// n = p + q + r for odd n >= 7 (Helfgott). r is the largest prime <= n - 4, which
+/// leaves a small even n - r with a Goldbach partition p + q.
+///
+pub fn ternary_goldbach(n: &BigInt, rng: &mut StdRng) -> Result<(BigInt, BigInt, BigInt), String> {
+    if n.is_even() || n < &BigInt::from(7u64) {
+        return Err(format!(
+            "error: {} is not an odd number >= 7, see goldbach for even n\n",
+            n
+        ));
+    }
+    let mut r = n - 4u32;
+    while !miller_rabin_primality(&r, rng) {
+        r -= 2u32;
+    }
+    let (p, q) = goldbach_minimal(&(n - &r), rng)?;
+    Ok((p, q, r))
+}
+
+///
+/// p = a² + b² for a prime p = 2 or p ≡ 1 (mod 4) by Cornacchia's algorithm
+///
+/// 1. x² ≡ -1 (mod p) with x < p/2, from the modular square root
+/// 2. run the Euclidean algorithm on (p, x), the first remainder a < √p is the answer
+/// 3. b = √(p - a²)
+///
+pub fn cornacchia(p: &BigInt) -> Option<(BigInt, BigInt)> {
+    if p == &BigInt::from(2u64) {
+        return Some((BigInt::one(), BigInt::one()));
+    }
+    let mut x = sqrt_mod_prime(&(p - 1u32), p)?;
+    if &x * 2u32 > *p {
+        x = p - x;
+    }
+
+    let (mut r0, mut r1) = (p.clone(), x);
+    while &r1 * &r1 > *p {
+        let r2 = &r0 % &r1;
+        r0 = r1;
+        r1 = r2;
+    }
+    let rest = p - &r1 * &r1;
+    let b = rest.sqrt();
+    if &b * &b == rest {
+        Some((r1, b))
+    } else {
+        None
+    }
+}
+
+///
+/// n = a² + b², a >= b >= 0, from the prime factorisation of n. Each prime p ≡ 1 (mod 4)
+/// splits as (a + bi)(a - bi) over the Gaussian integers and 2 = -i(1 + i)², so the
+/// product of the Cornacchia representations gives n. None when a prime q ≡ 3 (mod 4)
+/// divides n to an odd power.
+///
+pub fn two_squares(factors: &[(BigInt, usize)]) -> Option<(BigInt, BigInt)> {
+    let (mut a, mut b) = (BigInt::one(), BigInt::zero());
+    let four = BigInt::from(4u64);
+    for (p, e) in factors {
+        if p.mod_floor(&four) == BigInt::from(3u64) {
+            if e % 2 == 1 {
+                return None;
+            }
+            a *= p.pow(*e as u32 / 2);
+            b *= p.pow(*e as u32 / 2);
+            continue;
+        }
+        let (c, d) = cornacchia(p)?;
+        for _ in 0..*e {
+            (a, b) = (&a * &c - &b * &d, &a * &d + &b * &c);
+        }
+    }
+    let (a, b) = (a.abs(), b.abs());
+    Some(if a >= b { (a, b) } else { (b, a) })
+}
+
+///
+/// Every n = a² + b² with a >= b >= 0 from the prime factorisation of n, largest a first.
+/// A prime p = (c + di)(c - di) with exponent e splits among the two conjugates in e + 1
+/// ways, (c + di)ʲ(c - di)ᵉ⁻ʲ, and the choices for the different primes multiply. 2 and
+/// the q ≡ 3 (mod 4) contribute the same factor to each. Empty when a q ≡ 3 (mod 4)
+/// divides n to an odd power.
+///
+pub fn two_square_representations(factors: &[(BigInt, usize)]) -> Vec<(BigInt, BigInt)> {
+    let mul = |(a, b): &(BigInt, BigInt), (c, d): &(BigInt, BigInt)| (a * c - b * d, a * d + b * c);
+    let mut representations = vec![(BigInt::one(), BigInt::zero())];
+    let four = BigInt::from(4u64);
+    for (p, e) in factors {
+        let choices = if p.mod_floor(&four) == BigInt::from(3u64) {
+            if e % 2 == 1 {
+                return Vec::new();
+            }
+            vec![(p.pow(*e as u32 / 2), BigInt::zero())]
+        } else if p == &BigInt::from(2u64) {
+            // (1 + i)ᵉ and (1 - i)ᵉ differ by a unit
+            let one_i = (BigInt::one(), BigInt::one());
+            vec![(0..*e).fold((BigInt::one(), BigInt::zero()), |z, _| mul(&z, &one_i))]
+        } else {
+            let Some((c, d)) = cornacchia(p) else {
+                return Vec::new();
+            };
+            let (z, z_bar) = ((c.clone(), d.clone()), (c, -d));
+            (0..=*e)
+                .map(|j| {
+                    (0..*e).fold((BigInt::one(), BigInt::zero()), |w, k| {
+                        mul(&w, if k < j { &z } else { &z_bar })
+                    })
+                })
+                .collect()
+        };
+        representations = representations
+            .iter()
+            .flat_map(|w| choices.iter().map(move |z| mul(w, z)))
+            .collect();
+    }
+
+    let mut representations = representations
+        .into_iter()
+        .map(|(a, b)| {
+            let (a, b) = (a.abs(), b.abs());
+            if a >= b {
+                (a, b)
+            } else {
+                (b, a)
+            }
+        })
+        .collect::<Vec<(BigInt, BigInt)>>();
+    representations.sort_by(|x, y| y.cmp(x));
+    representations.dedup();
+    representations
+}
+
+///
+/// Every n = a² + b² with a >= b >= 0 by trying a from √n down to √(n/2), largest a first
+///
+pub fn two_square_search(n: &BigInt) -> Vec<(BigInt, BigInt)> {
+    let mut representations = Vec::new();
+    let mut a = n.sqrt();
+    while &(&a * &a * 2u32) >= n {
+        let rest = n - &a * &a;
+        let b = rest.sqrt();
+        if &b * &b == rest {
+            representations.push((a.clone(), b));
+        }
+        a -= 1u32;
+    }
+    representations
+}
+
+///
+/// Euler's factorisation of n from two representations as a sum of two squares, with a
+/// row per pair of the first representation and another one
+///
+#[derive(Clone, Debug)]
+pub struct EulerFactorisation {
+    pub representations: Vec<(BigInt, BigInt)>,
+    pub searched: bool,
+    pub pairs: Vec<EulerFactorTable>,
+    pub factor: Option<BigInt>,
+}
+
+///
+/// Euler's method: n = a² + b² = c² + d² gives
+///
+/// (ad - bc)(ad + bc) = a²d² - b²c² = (n - b²)d² - b²(n - d²) = n(d² - b²)
+///
+/// so n divides (ad - bc)(ad + bc), while 0 < |ad ± bc| < n for two different
+/// representations, and gcd(ad - bc, n) is a proper factor. The representations are
+/// searched for up to 10¹², above that they come from the factorisation of n, which
+/// makes the method a demonstration. Err when n cannot be factored.
+///
+pub fn euler_factorisation(n: &BigInt, rng: &mut StdRng) -> Result<EulerFactorisation, String> {
+    let searched = n <= &BigInt::from(TWO_SQUARES_SEARCH_LIMIT);
+    let representations = if searched {
+        two_square_search(n)
+    } else {
+        let (factors, unfactored) = factorise(n, rng);
+        if !unfactored.is_empty() {
+            return Err(format!("error: {} could not be factored\n", n));
+        }
+        two_square_representations(&factors)
+    };
+
+    let mut factor = None;
+    let mut pairs = Vec::new();
+    if let Some((a, b)) = representations.first() {
+        for (c, d) in representations.iter().skip(1) {
+            let (minus, plus) = (a * d - b * c, a * d + b * c);
+            let (g, h) = (minus.abs().gcd(n), plus.gcd(n));
+            pairs.push(EulerFactorTable::new(
+                format!("{}² + {}²", a, b),
+                format!("{}² + {}²", c, d),
+                minus.to_string(),
+                g.to_string(),
+                plus.to_string(),
+                h.to_string(),
+            ));
+            if factor.is_none() && !g.is_one() && &g != n {
+                factor = Some(g);
+            }
+        }
+    }
+    Ok(EulerFactorisation {
+        representations,
+        searched,
+        pairs,
+        factor,
+    })
+}
+
+///
+/// r₂(n), the number of ordered pairs of integers (a, b) with a² + b² = n:
+/// 4 ∏(e + 1) over the primes p ≡ 1 (mod 4), 0 if some q ≡ 3 (mod 4) has an odd exponent
+///
+pub fn r2(factors: &[(BigInt, usize)]) -> BigInt {
+    let four = BigInt::from(4u64);
+    let mut r = four.clone();
+    for (p, e) in factors {
+        let residue = p.mod_floor(&four);
+        if residue.is_one() {
+            r *= e + 1;
+        } else if residue == BigInt::from(3u64) && e % 2 == 1 {
+            return BigInt::zero();
+        }
+    }
+    r
+}
+
+///
+/// r₄(n), the number of ordered quadruples with a² + b² + c² + d² = n (Jacobi):
+/// 8σ(n) for odd n, 24σ(m) for even n = 2ᵏm with m odd
+///
+pub fn r4(factors: &[(BigInt, usize)]) -> BigInt {
+    let two = BigInt::from(2u64);
+    let sigma_odd: BigInt = factors
+        .iter()
+        .filter(|(p, _)| p != &two)
+        .map(|(p, e)| (p.pow(*e as u32 + 1) - 1u32) / (p - 1u32))
+        .product();
+    if factors.iter().any(|(p, _)| p == &two) {
+        sigma_odd * 24u32
+    } else {
+        sigma_odd * 8u32
+    }
+}
+
+///
+/// x uniform in [0, bound] with the given parity, None if there is none
+///
+fn random_with_parity(rng: &mut StdRng, bound: &BigInt, odd: bool) -> Option<BigInt> {
+    let x = generate_random_int_in_range(rng, &BigInt::zero(), &(bound + 1u32));
+    if x.is_odd() == odd {
+        Some(x)
+    } else if &x < bound {
+        Some(x + 1u32)
+    } else if x.is_positive() {
+        Some(x - 1u32)
+    } else {
+        None
+    }
+}
+
+///
+/// n = a² + b² + c² + d² by the randomised algorithm of Rabin and Shallit
+///
+/// Write n = 4ᵏm with 4 ∤ m and draw a, b at random until p = m - a² - b² is 1 or a
+/// prime ≡ 1 (mod 4), which Cornacchia splits as p = c² + d². The parities of a and b
+/// are chosen so that p ≡ 1 (mod 4); p is prime with probability about 1/ln m.
+/// The representation of m is scaled by 2ᵏ.
+///
+pub fn four_squares(n: &BigInt, rng: &mut StdRng) -> [BigInt; 4] {
+    let (mut m, mut scale) = (n.clone(), BigInt::one());
+    while m.is_positive() && (&m % 4u32).is_zero() {
+        m >>= 2;
+        scale <<= 1;
+    }
+    if m.is_zero() {
+        return [0, 0, 0, 0].map(BigInt::from);
+    }
+
+    // a² + b² ≡ m - 1 (mod 4) is the number of odd squares
+    let odd_squares = (&m - 1u32).mod_floor(&BigInt::from(4u64));
+    loop {
+        let Some(a) = random_with_parity(rng, &m.sqrt(), odd_squares >= BigInt::one()) else {
+            continue;
+        };
+        let rest = &m - &a * &a;
+        let Some(b) = random_with_parity(rng, &rest.sqrt(), odd_squares == BigInt::from(2u64))
+        else {
+            continue;
+        };
+        let p = rest - &b * &b;
+        let (c, d) = if p.is_one() {
+            (BigInt::one(), BigInt::zero())
+        } else if miller_rabin_primality(&p, rng) {
+            cornacchia(&p).expect("p ≡ 1 (mod 4)")
+        } else {
+            continue;
+        };
+
+        let mut squares = [a, b, c, d].map(|x| x * &scale);
+        squares.sort_by(|x, y| y.cmp(x));
+        return squares;
+    }
+}
+
+///
+/// n, a² + b², a² + b² + c² + d², r₂(n) and r₄(n) for every n in [start, end], the
+/// factorisations from `factor_range`
+///
+pub fn sums_of_squares_table(
+    start: u64,
+    end: u64,
+    rng: &mut StdRng,
+) -> Result<Vec<SumsOfSquares>, String> {
+    Ok(factor_range(start, end)?
+        .into_iter()
+        .map(|(n, factors)| {
+            let factors = factors
+                .into_iter()
+                .map(|(p, e)| (BigInt::from(p), e as usize))
+                .collect::<Vec<(BigInt, usize)>>();
+            SumsOfSquares::new(&BigInt::from(n), &factors, rng)
+        })
+        .collect())
+}
+
+///
+/// The decompositions of n into two and four squares with their counts
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SumsOfSquares {
+    pub n: BigInt,
+    pub two: Option<(BigInt, BigInt)>,
+    pub four: [BigInt; 4],
+    pub r2: BigInt,
+    pub r4: BigInt,
+}
+
+impl SumsOfSquares {
+    pub fn new(n: &BigInt, factors: &[(BigInt, usize)], rng: &mut StdRng) -> Self {
+        Self {
+            n: n.clone(),
+            two: two_squares(factors),
+            four: four_squares(n, rng),
+            r2: r2(factors),
+            r4: r4(factors),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::seeded_rng;
+
+    #[test]
+    fn test_goldbach() {
+        let (_, mut rng) = seeded_rng(Some(1));
+        assert_eq!(
+            goldbach_minimal(&BigInt::from(98u64), &mut rng),
+            Ok((BigInt::from(19u64), BigInt::from(79u64)))
+        );
+        assert!(goldbach_minimal(&BigInt::from(99u64), &mut rng).is_err());
+        // OEIS A002375
+        assert_eq!(goldbach_count(4, &mut rng), Ok(1));
+        assert_eq!(goldbach_count(100, &mut rng), Ok(6));
+        assert_eq!(goldbach_count(1000, &mut rng), Ok(28));
+
+        let n = BigInt::from(10u64).pow(30) + 1u32;
+        let (p, q, r) = ternary_goldbach(&n, &mut rng).unwrap();
+        assert_eq!(&p + &q + &r, n);
+        assert!([p, q, r]
+            .iter()
+            .all(|x| miller_rabin_primality(x, &mut rng)));
+    }
+
+    #[test]
+    fn test_cornacchia() {
+        for p in [2u64, 5, 13, 17, 29, 97, 1000000009] {
+            let (a, b) = cornacchia(&BigInt::from(p)).unwrap();
+            assert_eq!(&a * &a + &b * &b, BigInt::from(p));
+        }
+        assert_eq!(cornacchia(&BigInt::from(7u64)), None);
+    }
+
+    #[test]
+    fn test_two_square_representations() {
+        // 65 = 5 x 13 = 8² + 1² = 7² + 4², 1105 = 5 x 13 x 17 has four
+        let factors = |f: &[(u64, usize)]| {
+            f.iter()
+                .map(|&(p, e)| (BigInt::from(p), e))
+                .collect::<Vec<(BigInt, usize)>>()
+        };
+        let pairs = |r: &[(u64, u64)]| {
+            r.iter()
+                .map(|&(a, b)| (BigInt::from(a), BigInt::from(b)))
+                .collect::<Vec<(BigInt, BigInt)>>()
+        };
+        assert_eq!(
+            two_square_representations(&factors(&[(5, 1), (13, 1)])),
+            pairs(&[(8, 1), (7, 4)])
+        );
+        let n = BigInt::from(1105u64);
+        assert_eq!(
+            two_square_representations(&factors(&[(5, 1), (13, 1), (17, 1)])),
+            two_square_search(&n)
+        );
+        assert_eq!(two_square_search(&n).len(), 4);
+        // 2 x 3² x 5² = 450 = 21² + 3² = 15² + 15², 3 x 5 has none
+        assert_eq!(
+            two_square_representations(&factors(&[(2, 1), (3, 2), (5, 2)])),
+            pairs(&[(21, 3), (15, 15)])
+        );
+        assert_eq!(
+            two_square_search(&BigInt::from(450u64)),
+            pairs(&[(21, 3), (15, 15)])
+        );
+        assert!(two_square_representations(&factors(&[(3, 1), (5, 1)])).is_empty());
+    }
+
+    #[test]
+    fn test_euler_factorisation() {
+        let (_, mut rng) = seeded_rng(Some(3));
+        let euler = euler_factorisation(&BigInt::from(65u64), &mut rng).unwrap();
+        assert!(euler.searched);
+        assert_eq!(euler.factor, Some(BigInt::from(5u64)));
+
+        // (10¹² + 61)(10¹² + 121), both ≡ 1 (mod 4), through the factorisation
+        let (p, q) = (
+            BigInt::from(1000000000061u64),
+            BigInt::from(1000000000121u64),
+        );
+        let euler = euler_factorisation(&(&p * &q), &mut rng).unwrap();
+        assert!(!euler.searched);
+        assert_eq!(euler.representations.len(), 2);
+        let d = euler.factor.unwrap();
+        assert!(d == p || d == q);
+
+        let euler = euler_factorisation(&BigInt::from(1000000009u64), &mut rng).unwrap();
+        assert_eq!(euler.representations.len(), 1);
+        assert_eq!(euler.factor, None);
+    }
+
+    #[test]
+    fn test_sums_of_squares() {
+        let (_, mut rng) = seeded_rng(Some(7));
+        let table = sums_of_squares_table(1, 1000, &mut rng).unwrap();
+        for row in table.iter() {
+            if let Some((a, b)) = &row.two {
+                assert_eq!(a * a + b * b, row.n);
+            }
+            assert_eq!(row.two.is_some(), !row.r2.is_zero());
+            assert_eq!(row.four.iter().map(|x| x * x).sum::<BigInt>(), row.n);
+        }
+        // 25 = 5² + 0² = 4² + 3², with signs and order 12 pairs
+        assert_eq!(table[24].r2, BigInt::from(12u64));
+        assert_eq!(table[20].r2, BigInt::zero());
+        // r₄(1) = 8, r₄(2) = 24, r₄(10) = 24.σ(5) = 144
+        assert_eq!(table[0].r4, BigInt::from(8u64));
+        assert_eq!(table[1].r4, BigInt::from(24u64));
+        assert_eq!(table[9].r4, BigInt::from(144u64));
+
+        let n = BigInt::from(10u64).pow(40) + 7u32;
+        let squares = four_squares(&n, &mut rng);
+        assert_eq!(squares.iter().map(|x| x * x).sum::<BigInt>(), n);
+    }
+}
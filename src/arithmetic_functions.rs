@@ -0,0 +1,241 @@
+use rayon::prelude::*;
+
+/// Largest END of a factored range, the primes up to √END = 2²⁴ are sieved first.
+const FACTOR_RANGE_MAX_END: u64 = 1 << 48;
+/// Most numbers in a factored range, every one of them is held with its factorisation.
+const FACTOR_RANGE_MAX_LEN: u64 = 1 << 24;
+/// Numbers factored at a time by one thread.
+const FACTOR_RANGE_SEGMENT: u64 = 1 << 16;
+
+/// (p, e) for each prime power pᵉ dividing n
+pub type Factorisation = Vec<(u64, u32)>;
+
+///
+/// lp[n], the smallest prime factor of n for 2 <= n <= bound, by the linear sieve.
+/// Every composite n = lp[n].m is struck out exactly once, from m with the primes
+/// p <= lp[m], so the sieve runs in O(bound) time.
+///
+pub fn least_prime_factors(bound: u64) -> Vec<u32> {
+    let bound = bound as usize;
+    let mut lp = vec![0u32; bound + 1];
+    let mut primes: Vec<u32> = Vec::new();
+    for m in 2..=bound {
+        if lp[m] == 0 {
+            lp[m] = m as u32;
+            primes.push(m as u32);
+        }
+        for &p in primes.iter() {
+            if p > lp[m] || m * p as usize > bound {
+                break;
+            }
+            lp[m * p as usize] = p;
+        }
+    }
+    lp
+}
+
+///
+/// The factorisations of every n in [lo, hi]. Each prime p <= √hi is divided out of its
+/// multiples in the segment, what is left above 1 is a single prime > √hi.
+///
+fn factor_segment(lo: u64, hi: u64, primes: &[u64]) -> Vec<(u64, Factorisation)> {
+    let mut rest = (lo..=hi).collect::<Vec<u64>>();
+    let mut factors = vec![Factorisation::new(); rest.len()];
+    for &p in primes.iter().take_while(|&&p| p * p <= hi) {
+        for m in (lo.div_ceil(p) * p..=hi).step_by(p as usize) {
+            let i = (m - lo) as usize;
+            let mut e = 0;
+            while rest[i].is_multiple_of(p) {
+                rest[i] /= p;
+                e += 1;
+            }
+            factors[i].push((p, e));
+        }
+    }
+    for (f, r) in factors.iter_mut().zip(rest) {
+        if r > 1 {
+            f.push((r, 1));
+        }
+    }
+    (lo..=hi).zip(factors).collect()
+}
+
+///
+/// The classical arithmetic functions of n. φ, μ, σₖ and τ are multiplicative,
+/// λ(n) = (-1)^Ω(n) is completely multiplicative, ω and Ω are additive.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArithmeticFunctions {
+    pub phi: u64,
+    pub mu: i8,
+    pub sigma: u128,
+    pub tau: u64,
+    pub lambda: i8,
+    pub omega: u32,
+    pub big_omega: u32,
+}
+
+impl ArithmeticFunctions {
+    ///
+    /// Each function is built up from its values on the prime powers pᵉ:
+    /// φ = pᵉ⁻¹(p - 1), μ = -1 if e = 1 else 0, σₖ = 1 + pᵏ + ... + pᵉᵏ, τ = e + 1.
+    /// None when σₖ(n) overflows u128.
+    ///
+    fn from_factors(factors: &[(u64, u32)], k: u32) -> Option<Self> {
+        let mut values = Self {
+            phi: 1,
+            mu: 1,
+            sigma: 1,
+            tau: 1,
+            lambda: 1,
+            omega: factors.len() as u32,
+            big_omega: factors.iter().map(|(_, e)| e).sum(),
+        };
+        for &(p, e) in factors {
+            values.phi *= p.pow(e - 1) * (p - 1);
+            values.mu = if e == 1 { -values.mu } else { 0 };
+            values.tau *= e as u64 + 1;
+
+            let p_k = (p as u128).checked_pow(k)?;
+            let (mut sum, mut term) = (1u128, 1u128);
+            for _ in 0..e {
+                term = term.checked_mul(p_k)?;
+                sum = sum.checked_add(term)?;
+            }
+            values.sigma = values.sigma.checked_mul(sum)?;
+        }
+        if values.big_omega % 2 == 1 {
+            values.lambda = -1;
+        }
+        Some(values)
+    }
+}
+
+///
+/// The factorisations of every n in [start, end]. The primes up to √end come from the
+/// linear sieve, the range is then factored in segments, one per thread at a time.
+///
+pub fn factor_range(start: u64, end: u64) -> Result<Vec<(u64, Factorisation)>, String> {
+    if end > FACTOR_RANGE_MAX_END {
+        return Err(format!(
+            "error: END must not exceed {}\n",
+            FACTOR_RANGE_MAX_END
+        ));
+    }
+    let start = std::cmp::max(start, 1);
+    if start > end {
+        return Ok(Vec::new());
+    }
+    if end - start >= FACTOR_RANGE_MAX_LEN {
+        return Err(format!(
+            "error: END - START must be below {}\n",
+            FACTOR_RANGE_MAX_LEN
+        ));
+    }
+
+    let root = end.isqrt();
+    let lp = least_prime_factors(root);
+    let primes = (2..=root)
+        .filter(|&p| lp[p as usize] as u64 == p)
+        .collect::<Vec<u64>>();
+    Ok((start..=end)
+        .step_by(FACTOR_RANGE_SEGMENT as usize)
+        .collect::<Vec<u64>>()
+        .into_par_iter()
+        .flat_map_iter(|lo| {
+            let hi = std::cmp::min(lo + FACTOR_RANGE_SEGMENT - 1, end);
+            factor_segment(lo, hi, &primes)
+        })
+        .collect())
+}
+
+///
+/// φ, μ, σₖ, τ, λ, ω and Ω for every n in [start, end]. The range is factored by
+/// dividing each segment by the primes up to √end, see factor_range, and the rows are
+/// filled in parallel.
+///
+pub fn arithmetic_table(
+    start: u64,
+    end: u64,
+    k: u32,
+) -> Result<Vec<(u64, ArithmeticFunctions)>, String> {
+    factor_range(start, end)?
+        .into_par_iter()
+        .map(|(n, factors)| {
+            ArithmeticFunctions::from_factors(&factors, k)
+                .map(|values| (n, values))
+                .ok_or(format!("error: σ{}({}) overflows 128 bits\n", k, n))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_least_prime_factors() {
+        let lp = least_prime_factors(30);
+        assert_eq!(lp[2..12], [2, 3, 2, 5, 2, 7, 2, 3, 2, 11]);
+        assert_eq!(lp[25], 5);
+        assert_eq!(lp[29], 29);
+    }
+
+    #[test]
+    fn test_factor_range() {
+        let table = factor_range(1, 12).unwrap();
+        assert_eq!(table[0], (1, vec![]));
+        assert_eq!(table[11], (12, vec![(2, 2), (3, 1)]));
+        // 10¹² + 39 is prime, 10¹² + 40 = 2³.5.17573.1422637 has a factor above 10⁶
+        let n = 1_000_000_000_000u64;
+        let window = factor_range(n - 5, n + 40).unwrap();
+        assert_eq!(window.len(), 46);
+        for (m, factors) in &window {
+            let product = factors.iter().map(|&(p, e)| p.pow(e)).product::<u64>();
+            assert_eq!(product, *m);
+        }
+        assert_eq!(window[44], (n + 39, vec![(n + 39, 1)]));
+        assert_eq!(window[45].1, vec![(2, 3), (5, 1), (17573, 1), (1422637, 1)]);
+        assert!(factor_range(1, FACTOR_RANGE_MAX_END + 1).is_err());
+        assert!(factor_range(1, FACTOR_RANGE_MAX_LEN + 1).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic_table() {
+        let table = arithmetic_table(1, 12, 1).unwrap();
+        let phi = table.iter().map(|(_, f)| f.phi).collect::<Vec<u64>>();
+        assert_eq!(phi, vec![1, 1, 2, 2, 4, 2, 6, 4, 6, 4, 10, 4]);
+        let mu = table.iter().map(|(_, f)| f.mu).collect::<Vec<i8>>();
+        assert_eq!(mu, vec![1, -1, -1, 0, -1, 1, -1, 0, 0, 1, -1, 0]);
+        let sigma = table.iter().map(|(_, f)| f.sigma).collect::<Vec<u128>>();
+        assert_eq!(sigma, vec![1, 3, 4, 7, 6, 12, 8, 15, 13, 18, 12, 28]);
+
+        // 360 = 2³.3².5
+        let (_, f) = arithmetic_table(360, 360, 2).unwrap()[0];
+        assert_eq!(
+            f,
+            ArithmeticFunctions {
+                phi: 96,
+                mu: 0,
+                sigma: 85 * 91 * 26,
+                tau: 24,
+                lambda: 1,
+                omega: 3,
+                big_omega: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_table_counts_coprimes() {
+        use crate::groups_modulo_n::euler_totient_phi_counting_coprimes;
+        use num_bigint::BigInt;
+
+        for (n, f) in arithmetic_table(2, 300, 1).unwrap() {
+            assert_eq!(
+                BigInt::from(f.phi),
+                euler_totient_phi_counting_coprimes(&BigInt::from(n))
+            );
+        }
+    }
+}
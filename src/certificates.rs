@@ -0,0 +1,354 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    primality::baillie_psw,
+    prime_factors::PrimeFactors,
+    utils::{modular_pow, Gcd},
+};
+
+/// Primes below this bound are certified by trial division.
+const TRIAL_DIVISION_BOUND: u64 = 1 << 16;
+/// Trial division bound used to (partially) factor n - 1.
+const FACTOR_BOUND: u64 = 1 << 20;
+/// Largest base tried when searching for a witness.
+const MAX_WITNESS: u64 = 10000;
+
+///
+/// A certificate of primality for n, with recursive certificates for the prime factors
+/// of n - 1 it relies on. Numbers are kept as decimal strings so that the JSON can be
+/// read and checked by hand.
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Certificate {
+    /// n < 2¹⁶ has no divisor 2 <= d <= √n
+    TrialDivision { n: String },
+    /// aⁿ⁻¹ ≡ 1 (mod n) and a^((n-1)/q) ≢ 1 (mod n) for every prime q | n - 1,
+    /// so a has order n - 1 and n is prime
+    Pratt {
+        n: String,
+        a: String,
+        factors: Vec<FactorCertificate>,
+    },
+    /// n - 1 = F.R with F fully factored and F² > n. For each prime q | F the witness
+    /// a satisfies aⁿ⁻¹ ≡ 1 (mod n) and gcd(a^((n-1)/q) - 1, n) = 1, so every prime
+    /// factor of n is ≡ 1 (mod F), hence > √n
+    Pocklington {
+        n: String,
+        factors: Vec<FactorCertificate>,
+        unfactored: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FactorCertificate {
+    q: String,
+    exp: usize,
+    /// Pocklington witness for q. Pratt certificates use a single base for all factors.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    a: Option<String>,
+    certificate: Certificate,
+}
+
+impl Certificate {
+    pub fn n(&self) -> &str {
+        match self {
+            Certificate::TrialDivision { n }
+            | Certificate::Pratt { n, .. }
+            | Certificate::Pocklington { n, .. } => n,
+        }
+    }
+}
+
+///
+/// Fully factor n - 1, proving the large cofactor prime if there is one.
+///
+fn factor_n_minus_one(n: &BigInt) -> (Vec<(BigInt, usize)>, BigInt) {
+    let (mut factors, rest) = (n - 1u32).partial_prime_factors(FACTOR_BOUND);
+    if !rest.is_one() && baillie_psw(&rest) {
+        factors.push((rest, 1));
+        return (factors, BigInt::one());
+    }
+    (factors, rest)
+}
+
+fn certify_factors(
+    factors: &[(BigInt, usize)],
+    witnesses: Option<&[BigInt]>,
+    pocklington: bool,
+) -> Result<Vec<FactorCertificate>, String> {
+    factors
+        .iter()
+        .enumerate()
+        .map(|(i, (q, exp))| {
+            Ok(FactorCertificate {
+                q: q.to_string(),
+                exp: *exp,
+                a: witnesses.map(|w| w[i].to_string()),
+                certificate: certify(q, pocklington)?,
+            })
+        })
+        .collect()
+}
+
+///
+/// Pratt certificate: search for a base a of order n - 1 (a primitive root of n).
+///
+pub fn pratt_certificate(n: &BigInt) -> Result<Certificate, String> {
+    let n_minus_one = n - 1u32;
+    let (factors, rest) = factor_n_minus_one(n);
+    if !rest.is_one() {
+        return Err(format!(
+            "Pratt: could not fully factor n - 1, {} is left unfactored\n",
+            rest
+        ));
+    }
+
+    let a = (2..MAX_WITNESS)
+        .map(BigInt::from)
+        .find(|a| {
+            modular_pow(a, &n_minus_one, n).is_one()
+                && factors
+                    .iter()
+                    .all(|(q, _)| !modular_pow(a, &(&n_minus_one / q), n).is_one())
+        })
+        .ok_or(format!(
+            "Pratt: no primitive root of {} below {}\n",
+            n, MAX_WITNESS
+        ))?;
+
+    Ok(Certificate::Pratt {
+        n: n.to_string(),
+        a: a.to_string(),
+        factors: certify_factors(&factors, None, false)?,
+    })
+}
+
+///
+/// Pocklington-Lehmer certificate from a partial factorisation n - 1 = F.R, F > √n.
+///
+pub fn pocklington_certificate(n: &BigInt) -> Result<Certificate, String> {
+    let n_minus_one = n - 1u32;
+    let (factors, rest) = factor_n_minus_one(n);
+    let f = &n_minus_one / &rest;
+    if &f * &f <= *n {
+        return Err(format!(
+            "Pocklington: the factored part {} of n - 1 is not larger than √n\n",
+            f
+        ));
+    }
+
+    let witnesses = factors
+        .iter()
+        .map(|(q, _)| {
+            (2..MAX_WITNESS)
+                .map(BigInt::from)
+                .find(|a| {
+                    modular_pow(a, &n_minus_one, n).is_one()
+                        && (modular_pow(a, &(&n_minus_one / q), n) - 1u32).gcd_euclid(n)
+                            == BigInt::one()
+                })
+                .ok_or(format!(
+                    "Pocklington: no witness for q = {} below {}\n",
+                    q, MAX_WITNESS
+                ))
+        })
+        .collect::<Result<Vec<BigInt>, String>>()?;
+
+    Ok(Certificate::Pocklington {
+        n: n.to_string(),
+        factors: certify_factors(&factors, Some(&witnesses), true)?,
+        unfactored: rest.to_string(),
+    })
+}
+
+///
+/// Certificate of primality for n, recursing on the prime factors of n - 1 until they
+/// are small enough for trial division. Pratt certificates need n - 1 to be fully
+/// factored, Pocklington certificates only the part above √n.
+///
+pub fn certify(n: &BigInt, pocklington: bool) -> Result<Certificate, String> {
+    if n < &BigInt::from(2u64) || !baillie_psw(n) {
+        return Err(format!("{} is not prime\n", n));
+    }
+    if n < &BigInt::from(TRIAL_DIVISION_BOUND) {
+        return Ok(Certificate::TrialDivision { n: n.to_string() });
+    }
+    if pocklington {
+        pocklington_certificate(n)
+    } else {
+        pratt_certificate(n)
+    }
+}
+
+fn parse(s: &str) -> Result<BigInt, String> {
+    s.parse::<BigInt>()
+        .map_err(|_| format!("'{}' is not an integer\n", s))
+}
+
+///
+/// Verify the factor list against n - 1 and each factor's certificate. Returns the
+/// product of the prime powers.
+///
+fn verify_factors(n: &BigInt, factors: &[FactorCertificate]) -> Result<BigInt, String> {
+    let mut f = BigInt::one();
+    for factor in factors {
+        let q = parse(&factor.q)?;
+        if factor.certificate.n() != factor.q {
+            return Err(format!(
+                "certificate for {} proves {} instead\n",
+                factor.q,
+                factor.certificate.n()
+            ));
+        }
+        verify_certificate(&factor.certificate)?;
+        if factor.exp == 0 || !((n - 1u32) % q.pow(factor.exp as u32)).is_zero() {
+            return Err(format!("{}^{} does not divide {} - 1\n", q, factor.exp, n));
+        }
+        f *= q.pow(factor.exp as u32);
+    }
+    Ok(f)
+}
+
+///
+/// Independently check a certificate and all the certificates nested in it.
+/// Returns the proven prime.
+///
+pub fn verify_certificate(certificate: &Certificate) -> Result<BigInt, String> {
+    let n = parse(certificate.n())?;
+    if n < BigInt::from(2u64) {
+        return Err(format!("{} is not prime\n", n));
+    }
+    let n_minus_one = &n - 1u32;
+
+    match certificate {
+        Certificate::TrialDivision { .. } => {
+            if n >= BigInt::from(TRIAL_DIVISION_BOUND) {
+                return Err(format!(
+                    "{} is too large to be certified by trial division\n",
+                    n
+                ));
+            }
+            let mut d = BigInt::from(2u64);
+            while &d * &d <= n {
+                if (&n % &d).is_zero() {
+                    return Err(format!("{} is divisible by {}\n", n, d));
+                }
+                d += 1u32;
+            }
+        }
+        Certificate::Pratt { a, factors, .. } => {
+            let a = parse(a)?;
+            if verify_factors(&n, factors)? != n_minus_one {
+                return Err(format!("Pratt: the factors do not multiply to {} - 1\n", n));
+            }
+            if !modular_pow(&a, &n_minus_one, &n).is_one() {
+                return Err(format!("Pratt: {}^({} - 1) ≢ 1 (mod {})\n", a, n, n));
+            }
+            for factor in factors {
+                let q = parse(&factor.q)?;
+                if modular_pow(&a, &(&n_minus_one / &q), &n).is_one() {
+                    return Err(format!(
+                        "Pratt: {}^(({} - 1)/{}) ≡ 1 (mod {}), {} is not a primitive root\n",
+                        a, n, q, n, a
+                    ));
+                }
+            }
+        }
+        Certificate::Pocklington {
+            factors,
+            unfactored,
+            ..
+        } => {
+            let f = verify_factors(&n, factors)?;
+            if &f * parse(unfactored)? != n_minus_one {
+                return Err(format!(
+                    "Pocklington: F.R = {} x {} is not {} - 1\n",
+                    f, unfactored, n
+                ));
+            }
+            if &f * &f <= n {
+                return Err(format!(
+                    "Pocklington: F = {} is not larger than √{}\n",
+                    f, n
+                ));
+            }
+            for factor in factors {
+                let q = parse(&factor.q)?;
+                let a = parse(
+                    factor
+                        .a
+                        .as_deref()
+                        .ok_or(format!("Pocklington: missing witness for q = {}\n", q))?,
+                )?;
+                if !modular_pow(&a, &n_minus_one, &n).is_one() {
+                    return Err(format!("Pocklington: {}^({} - 1) ≢ 1 (mod {})\n", a, n, n));
+                }
+                let g = (modular_pow(&a, &(&n_minus_one / &q), &n) - 1u32).gcd(&n);
+                if !g.is_one() {
+                    return Err(format!(
+                        "Pocklington: gcd({}^(({} - 1)/{}) - 1, {}) = {}\n",
+                        a, n, q, n, g
+                    ));
+                }
+            }
+        }
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pratt_certificate() {
+        // 2⁶¹ - 1, n - 1 = 2 x 3² x 5² x 7 x 11 x 13 x 31 x 41 x 61 x 151 x 331 x 1321
+        let n = (BigInt::one() << 61) - 1;
+        let cert = certify(&n, false).unwrap();
+        assert!(matches!(cert, Certificate::Pratt { .. }));
+        assert_eq!(verify_certificate(&cert).unwrap(), n);
+    }
+
+    #[test]
+    fn test_pocklington_certificate() {
+        // 10³⁰ + 21127 is prime, n - 1 = F.R with R = 285207650217251 left unfactored
+        let n = BigInt::from(10u64).pow(30) + 21127;
+        let cert = certify(&n, true).unwrap();
+        assert_eq!(verify_certificate(&cert).unwrap(), n);
+        assert!(matches!(
+            &cert,
+            Certificate::Pocklington { unfactored, .. } if unfactored == "285207650217251"
+        ));
+        assert!(certify(&n, false).is_err());
+
+        let json = serde_json::to_string(&cert).unwrap();
+        let parsed: Certificate = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, cert);
+
+        assert!(certify(&BigInt::from(1000001u64), true).is_err());
+    }
+
+    #[test]
+    fn test_verify_certificate_rejects_forgery() {
+        // 65537 x 65539 passed off as a prime using Pratt's conditions
+        let n = BigInt::from(65537u64) * BigInt::from(65539u64);
+        let forged = Certificate::Pratt {
+            n: n.to_string(),
+            a: "3".to_string(),
+            factors: vec![FactorCertificate {
+                q: "2".to_string(),
+                exp: 1,
+                a: None,
+                certificate: Certificate::TrialDivision { n: "2".to_string() },
+            }],
+        };
+        assert!(verify_certificate(&forged).is_err());
+
+        let too_large = Certificate::TrialDivision { n: n.to_string() };
+        assert!(verify_certificate(&too_large).is_err());
+    }
+}
@@ -7,9 +7,28 @@ pub enum PrimalityMethods {
     Fermat,
     Gcd,
     MillerRabin,
+    SolovayStrassen,
+    BailliePsw,
     AKS,
 }
 
+#[derive(clap::ValueEnum, Clone, Debug)]
+pub enum CertificateMethods {
+    Pratt,
+    Pocklington,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
+pub enum ArithmeticFunction {
+    Phi,
+    Mu,
+    Sigma,
+    Tau,
+    Lambda,
+    Omega,
+    BigOmega,
+}
+
 #[derive(clap::ValueEnum, Clone, Debug)]
 pub enum CarmichaelMethods {
     Fermat,
@@ -66,6 +85,181 @@ pub fn cli() -> Command {
                 .about("Search for composite numbers between START and END numbers")
                 .help_template(APP_TEMPLATE),
         )
+        .subcommand(
+            Command::new("next-prime")
+                .arg(
+                    arg!(-n --num <NUM>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(BigInt)),
+                )
+                .about("Smallest prime greater than or equal to NUM")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("prime-count")
+                .arg(Arg::new("x")
+                    .short('x')
+                    .long("x")
+                    .required(true)
+                    .num_args(1..)
+                    .value_delimiter(',')
+                    .value_parser(clap::value_parser!(u64))
+                    .help("One or more values of x, comma or space separated"),
+                )
+                .arg(Arg::new("estimates")
+                    .long("estimates")
+                    .action(ArgAction::SetTrue)
+                    .help("Add the columns x/ln x, li(x) and their relative errors"),
+                )
+                .about("Prime counting function π(x) by the Lucy_Hedgehog method, 2 <= x <= 10¹⁴")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("nth-prime")
+                .arg(Arg::new("n")
+                    .short('n')
+                    .long("n")
+                    .required(true)
+                    .num_args(1..)
+                    .value_delimiter(',')
+                    .value_parser(clap::value_parser!(u64))
+                    .help("One or more indices n, comma or space separated"),
+                )
+                .about("The nth prime pₙ, located with π(x) and the sieve")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("prime-gaps")
+                .arg(
+                    arg!(-s --start <START>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(
+                    arg!(-e --end <END>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(Arg::new("histogram")
+                    .long("histogram")
+                    .action(ArgAction::SetTrue)
+                    .help("Also print how often each gap occurs"),
+                )
+                .about("Record and maximal gaps between consecutive primes between START and END numbers")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("prime-tuples")
+                .arg(
+                    arg!(-s --start <START>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(
+                    arg!(-e --end <END>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(Arg::new("pattern")
+                    .short('p')
+                    .long("pattern")
+                    .default_value("twins")
+                    .help("twins, cousins, sexy or admissible offsets such as 0,2,6,8"),
+                )
+                .about("Prime constellations p, p + b₂, ..., p + bₖ with p between START and END numbers")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("arith-table")
+                .arg(
+                    arg!(-s --start <START>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(
+                    arg!(-e --end <END>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(Arg::new("functions")
+                    .short('f')
+                    .long("functions")
+                    .num_args(1..)
+                    .value_delimiter(',')
+                    .default_value("phi,mu,sigma,tau,lambda,omega,big-omega")
+                    .value_parser(clap::builder::EnumValueParser::<ArithmeticFunction>::new())
+                    .help("Columns of the table, comma or space separated"),
+                )
+                .arg(Arg::new("k")
+                    .short('k')
+                    .long("k")
+                    .default_value("1")
+                    .value_parser(clap::value_parser!(u32))
+                    .help("Power k of the divisor function σₖ(n)"),
+                )
+                .about("Table of φ, μ, σₖ, τ, λ, ω and Ω between START and END numbers factored in segments")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("goldbach")
+                .arg(Arg::new("num")
+                    .short('n')
+                    .long("num")
+                    .required_unless_present("start")
+                    .conflicts_with_all(["start", "end"])
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(Arg::new("start")
+                    .short('s')
+                    .long("start")
+                    .requires("end")
+                    .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(Arg::new("end")
+                    .short('e')
+                    .long("end")
+                    .requires("start")
+                    .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(Arg::new("seed")
+                    .long("seed")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Seed for Miller-Rabin above 3.3·10²⁴, overrides the session seed")
+                )
+                .about("Goldbach partitions n = p + q of even NUM with their count, n = p + q + r for odd NUM, or a table of the partitions between START and END")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("squares")
+                .arg(Arg::new("num")
+                    .short('n')
+                    .long("num")
+                    .required_unless_present("start")
+                    .conflicts_with_all(["start", "end"])
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(Arg::new("start")
+                    .short('s')
+                    .long("start")
+                    .requires("end")
+                    .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(Arg::new("end")
+                    .short('e')
+                    .long("end")
+                    .requires("start")
+                    .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(Arg::new("seed")
+                    .long("seed")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Seed for the random squares of Rabin-Shallit, overrides the session seed")
+                )
+                .about("NUM as a sum of two squares (Cornacchia) and four squares (Rabin-Shallit) with r₂(n) and r₄(n), or a table between START and END")
+                .help_template(APP_TEMPLATE),
+        )
         .subcommand(
             Command::new("composites-pq")
                 .arg(
@@ -140,6 +334,12 @@ pub fn cli() -> Command {
                     .num_args(0)
                     .requires("NUM2")
                 )
+                .arg(Arg::new("seed")
+                    .long("seed")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Seed for Pollard's rho on large cofactors, overrides the session seed")
+                )
                 .about("Finds the Integer Factorisation of a number.")
                 .help_template(APP_TEMPLATE),
         )
@@ -157,6 +357,29 @@ pub fn cli() -> Command {
                     .required(true)
                     .value_parser(clap::value_parser!(BigInt))
                 )
+                .arg(Arg::new("bases")
+                    .short('a')
+                    .long("bases")
+                    .required(false)
+                    .num_args(1..)
+                    .value_delimiter(',')
+                    .value_parser(clap::value_parser!(BigInt))
+                    .help("Comma separated list of bases to test (fermat, miller-rabin, solovay-strassen)")
+                )
+                .arg(Arg::new("rounds")
+                    .short('r')
+                    .long("rounds")
+                    .required(false)
+                    .default_value("5")
+                    .value_parser(clap::value_parser!(u8))
+                    .help("Number of random bases to try when no bases are passed (miller-rabin: only above 3.3·10²⁴)")
+                )
+                .arg(Arg::new("seed")
+                    .long("seed")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Seed for the random bases, overrides the session seed")
+                )
                 .about("Primality checking capabilities.")
                 .help_template(APP_TEMPLATE),
         )
@@ -171,6 +394,17 @@ pub fn cli() -> Command {
                 .about("List the Miller-Rabin Liars of a number if any exist")
                 .help_template(APP_TEMPLATE),
         )
+        .subcommand(
+            Command::new("euler-jacobi-liars")
+                .arg(Arg::new("num")
+                    .short('n')
+                    .long("num")
+                    .required(true)
+                    .value_parser(clap::value_parser!(BigInt))
+                )
+                .about("List the Euler-Jacobi (Solovay-Strassen) Liars of a number if any exist")
+                .help_template(APP_TEMPLATE),
+        )
         .subcommand(
             Command::new("gcd")
                 .arg(Arg::new("NUM1")
@@ -196,9 +430,61 @@ pub fn cli() -> Command {
                     .required(true)
                     .value_parser(clap::value_parser!(BigInt)),
                 )
+                .arg(Arg::new("bound")
+                    .short('b')
+                    .long("bound")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Smoothness bound B of the factor base, at least 30, defaults to L(n)¹ᐟ²"),
+                )
                 .about("Integer Factorisation - Quadratic Sieve.")
                 .help_template(APP_TEMPLATE),
         )
+        .subcommand(
+            Command::new("siqs")
+                .arg(Arg::new("NUM")
+                    .short('n')
+                    .long("num")
+                    .required(true)
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(Arg::new("fb-size")
+                    .long("fb-size")
+                    .required(false)
+                    .value_parser(clap::value_parser!(usize))
+                    .help("Number of primes in the factor base, at least 60, defaults to a table by the size of n"),
+                )
+                .arg(Arg::new("checkpoint")
+                    .short('c')
+                    .long("checkpoint")
+                    .required(false)
+                    .help("File the relations are appended to, a run with the same file resumes from them"),
+                )
+                .arg(Arg::new("seed")
+                    .long("seed")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Seed for the choice of the A coefficients, overrides the session seed")
+                )
+                .about("Integer Factorisation - Self-Initialising Quadratic Sieve")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("gf2-nullspace")
+                .arg(Arg::new("file")
+                    .required(true)
+                    .value_hint(ValueHint::FilePath)
+                    .help("Relations a line each: the columns of the odd exponents, or a siqs checkpoint"),
+                )
+                .arg(Arg::new("output")
+                    .short('o')
+                    .long("output")
+                    .value_hint(ValueHint::FilePath)
+                    .help("Write the dependencies to a file, the line numbers of each on a line"),
+                )
+                .about("Dependencies among the relations of a sieve over GF(2) by filtering and Block Lanczos")
+                .help_template(APP_TEMPLATE),
+        )
         .subcommand(
             Command::new("pollards-p-minus-1")
                 .arg(Arg::new("NUM")
@@ -210,10 +496,185 @@ pub fn cli() -> Command {
                 .arg(Arg::new("BASE")
                     .short('b')
                     .long("base")
+                    .default_value("2")
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(Arg::new("b1")
+                    .long("b1")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Stage 1 bound B1, defaults to n¹ᐟ⁶ within [10³, 10⁶]"),
+                )
+                .arg(Arg::new("b2")
+                    .long("b2")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Stage 2 bound B2, defaults to 100.B1"),
+                )
+                .about("Integer Factorisation - Pollard's P-1 Algm. with stage 2")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("williams-p-plus-1")
+                .arg(Arg::new("NUM")
+                    .short('n')
+                    .long("num")
+                    .required(true)
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(Arg::new("A")
+                    .short('a')
+                    .long("a")
+                    .default_value("3")
+                    .value_parser(clap::value_parser!(BigInt))
+                    .help("Starting value V₁ = A of the Lucas sequence, A + 1 and A + 2 are tried next"),
+                )
+                .arg(Arg::new("b1")
+                    .long("b1")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Stage 1 bound B1, defaults to n¹ᐟ⁶ within [10³, 10⁶]"),
+                )
+                .arg(Arg::new("b2")
+                    .long("b2")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Stage 2 bound B2, defaults to 100.B1"),
+                )
+                .about("Integer Factorisation - Williams' P+1 Algm. with stage 2")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("pollards-rho-factor")
+                .arg(Arg::new("NUM")
+                    .short('n')
+                    .long("num")
                     .required(true)
                     .value_parser(clap::value_parser!(BigInt)),
                 )
-                .about("Integer Factorisation - Pollard's P-1 Algm.")
+                .arg(Arg::new("seed")
+                    .long("seed")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Seed for the random c and starting point, overrides the session seed")
+                )
+                .about("Integer Factorisation - Pollard's Rho Algm. with Brent's cycle detection")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("fermat-factor")
+                .arg(Arg::new("NUM")
+                    .short('n')
+                    .long("num")
+                    .required(true)
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(Arg::new("multiplier")
+                    .short('k')
+                    .long("multiplier")
+                    .default_value("1")
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Run on kn, for factors p, q with q/p close to k"),
+                )
+                .arg(Arg::new("max-steps")
+                    .long("max-steps")
+                    .default_value("1000000")
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Values of x tried before giving up"),
+                )
+                .about("Integer Factorisation - Fermat's method, x² - kn = y²")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("lehman-factor")
+                .arg(Arg::new("NUM")
+                    .short('n')
+                    .long("num")
+                    .required(true)
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .about("Integer Factorisation - Lehman's O(n¹ᐟ³) method, for n up to 64 bits")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("hart-factor")
+                .arg(Arg::new("NUM")
+                    .short('n')
+                    .long("num")
+                    .required(true)
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(Arg::new("max-steps")
+                    .long("max-steps")
+                    .default_value("1000000")
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Multipliers i tried before giving up"),
+                )
+                .about("Integer Factorisation - Hart's one line factoring, s = ⌈√(in)⌉ with s² mod n a square")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("euler-factor")
+                .arg(Arg::new("NUM")
+                    .short('n')
+                    .long("num")
+                    .required(true)
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(Arg::new("seed")
+                    .long("seed")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Seed for the factorisation of n above 10¹², overrides the session seed")
+                )
+                .about("Integer Factorisation - Euler's method from two representations n = a² + b² = c² + d²")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("squfof")
+                .arg(Arg::new("NUM")
+                    .short('n')
+                    .long("num")
+                    .required(true)
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .about("Integer Factorisation - Shanks' square forms factorisation with multipliers, for n < 2⁶⁴")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("ecm")
+                .arg(Arg::new("NUM")
+                    .short('n')
+                    .long("num")
+                    .required(true)
+                    .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(Arg::new("b1")
+                    .long("b1")
+                    .default_value("11000")
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Stage 1 bound B1, 11000 suits factors of about 20 digits"),
+                )
+                .arg(Arg::new("b2")
+                    .long("b2")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Stage 2 bound B2, defaults to 100.B1"),
+                )
+                .arg(Arg::new("curves")
+                    .short('c')
+                    .long("curves")
+                    .default_value("25")
+                    .value_parser(clap::value_parser!(usize))
+                    .help("Number of curves to try, run in parallel"),
+                )
+                .arg(Arg::new("seed")
+                    .long("seed")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Seed for the random curve parameters σ, overrides the session seed")
+                )
+                .about("Integer Factorisation - Lenstra's Elliptic Curve Method on Montgomery curves")
                 .help_template(APP_TEMPLATE),
         )
         .subcommand(
@@ -350,6 +811,157 @@ in set B")
 test")
                 .help_template(APP_TEMPLATE),
         )
+        .subcommand(
+            Command::new("mersenne")
+                .arg(
+                    arg!(-s --start <START>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(
+                    arg!(-e --end <END>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(Arg::new("factors")
+                    .long("factors")
+                    .action(ArgAction::SetTrue)
+                    .help("Search composite Mₚ for a small factor of the form 2kp + 1"),
+                )
+                .arg(Arg::new("k-max")
+                    .long("k-max")
+                    .default_value("10000")
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Largest k tried when searching for factors 2kp + 1"),
+                )
+                .about("Lucas-Lehmer test of the Mersenne numbers 2ᵖ - 1 for the prime exponents p between START and END")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("pepin")
+                .arg(
+                    arg!(-s --start <START>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(u32)),
+                )
+                .arg(
+                    arg!(-e --end <END>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(u32)),
+                )
+                .about("Pépin's test of the Fermat numbers 2^(2ᵐ) + 1 for START <= m <= END <= 16")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("proth")
+                .arg(Arg::new("k")
+                    .short('k')
+                    .required(true)
+                    .value_parser(clap::value_parser!(BigInt))
+                    .help("Odd multiplier k < 2ⁿ"),
+                )
+                .arg(Arg::new("n")
+                    .short('n')
+                    .required(true)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Power of 2, at most 65536"),
+                )
+                .about("Proth's test of N = k.2ⁿ + 1 with a certificate of primality")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("gen-prime")
+                .arg(
+                    arg!(-b --bits <BITS>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(Arg::new("safe")
+                    .long("safe")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all(["strong", "sophie-germain"])
+                    .help("Safe prime p, (p - 1)/2 is prime"),
+                )
+                .arg(Arg::new("strong")
+                    .long("strong")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all(["safe", "sophie-germain", "cert"])
+                    .help("Strong prime by Gordon's algorithm, at least 64 bits"),
+                )
+                .arg(Arg::new("sophie-germain")
+                    .long("sophie-germain")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all(["safe", "strong"])
+                    .help("Sophie Germain prime p, 2p + 1 is prime"),
+                )
+                .arg(Arg::new("count")
+                    .short('c')
+                    .long("count")
+                    .default_value("1")
+                    .value_parser(clap::value_parser!(usize))
+                    .help("Number of primes to generate"),
+                )
+                .arg(Arg::new("cert")
+                    .long("cert")
+                    .action(ArgAction::SetTrue)
+                    .help("Construct provable primes and print a Pocklington certificate for each"),
+                )
+                .arg(Arg::new("seed")
+                    .long("seed")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Seed for the random starting points, overrides the session seed")
+                )
+                .about("Generate random primes of a given bit length")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("certify")
+                .arg(
+                    arg!(-n --num <NUM>)
+                        .required(true)
+                        .value_parser(clap::value_parser!(BigInt)),
+                )
+                .arg(
+                    arg!(-m --method <METHOD>)
+                        .default_value("pocklington")
+                        .value_parser(clap::builder::EnumValueParser::<CertificateMethods>::new()),
+                )
+                .arg(Arg::new("output")
+                    .short('o')
+                    .long("output")
+                    .value_hint(ValueHint::FilePath)
+                    .help("Write the certificate to a file instead of printing it"),
+                )
+                .about("Generate a Pratt or Pocklington-Lehmer certificate of primality as JSON")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("verify-cert")
+                .arg(Arg::new("file")
+                    .required(true)
+                    .value_hint(ValueHint::FilePath)
+                    .help("JSON certificate written by certify"),
+                )
+                .about("Independently check a certificate of primality")
+                .help_template(APP_TEMPLATE),
+        )
+        .subcommand(
+            Command::new("seed")
+                .arg(Arg::new("VALUE")
+                    .required(false)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Seed used by every randomised algorithm in this session"),
+                )
+                .arg(Arg::new("clear")
+                    .long("clear")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with("VALUE")
+                    .help("Draw a fresh seed for every command"),
+                )
+                .about("Set or show the seed of the random number generator for reproducible results.")
+                .help_template(APP_TEMPLATE),
+        )
         .subcommand(
             Command::new("clear")
                 .alias("cls")
@@ -17,7 +17,7 @@ use tabled::{
     Table, Tabled,
 };
 
-use crate::factorisations;
+use crate::{additive::SumsOfSquares, arithmetic_functions::ArithmeticFunctions, factorisations};
 
 const STYLE_2: Style<On, On, On, On, On, On, 0, 0> = Style::rounded()
     .line_horizontal(HorizontalLine::inherit(Style::modern()))
@@ -220,6 +220,346 @@ pub fn miller_rabin_output_print(table_data: &Vec<MillerRabinTable>) {
     // println!("{html_table}");
 }
 
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct FermatTable {
+    n: String,
+    a: String,
+    #[tabled(rename = "gcd(a, n)")]
+    gcd: String,
+    #[tabled(rename = "x = aⁿ⁻¹ (mod n)")]
+    a_raised_n_minus_one: String,
+    message: String,
+}
+
+impl FermatTable {
+    pub fn new(
+        n: String,
+        a: String,
+        gcd: String,
+        a_raised_n_minus_one: String,
+        message: String,
+    ) -> Self {
+        Self {
+            n,
+            a,
+            gcd,
+            a_raised_n_minus_one,
+            message,
+        }
+    }
+}
+
+pub fn fermat_output_print(table_data: &Vec<FermatTable>) {
+    let table = Table::new(table_data)
+        .with(Merge::vertical())
+        .with(Style::modern())
+        .with(BorderSpanCorrection)
+        .to_string();
+
+    println!("\n{}\n", table);
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct SolovayStrassenTable {
+    n: String,
+    a: String,
+    #[tabled(rename = "(a/n)")]
+    jacobi: String,
+    #[tabled(rename = "x = a⁽ⁿ⁻¹⁾ᐟ² (mod n)")]
+    a_raised_half_n_minus_one: String,
+    message: String,
+}
+
+impl SolovayStrassenTable {
+    pub fn new(
+        n: String,
+        a: String,
+        jacobi: String,
+        a_raised_half_n_minus_one: String,
+        message: String,
+    ) -> Self {
+        Self {
+            n,
+            a,
+            jacobi,
+            a_raised_half_n_minus_one,
+            message,
+        }
+    }
+}
+
+pub fn solovay_strassen_output_print(table_data: &Vec<SolovayStrassenTable>) {
+    let table = Table::new(table_data)
+        .with(Merge::vertical())
+        .with(Style::modern())
+        .with(BorderSpanCorrection)
+        .to_string();
+
+    println!("\n{}\n", table);
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct MersenneTable {
+    p: String,
+    #[tabled(rename = "Mₚ = 2ᵖ - 1")]
+    mersenne_num: String,
+    #[tabled(rename = "Lucas-Lehmer")]
+    result: String,
+    #[tabled(rename = "Factor q = 2kp + 1")]
+    factor: String,
+}
+
+impl MersenneTable {
+    pub fn new(p: String, mersenne_num: String, result: String, factor: String) -> Self {
+        Self {
+            p,
+            mersenne_num,
+            result,
+            factor,
+        }
+    }
+
+    pub fn get_result(&self) -> String {
+        self.result.clone()
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct PepinTable {
+    m: String,
+    #[tabled(rename = "Fₘ = 2^(2ᵐ) + 1")]
+    fermat_num: String,
+    #[tabled(rename = "3^((Fₘ - 1)/2) ≡ -1 (mod Fₘ)")]
+    congruent_minus_1: bool,
+    result: String,
+}
+
+impl PepinTable {
+    pub fn new(m: String, fermat_num: String, congruent_minus_1: bool, result: String) -> Self {
+        Self {
+            m,
+            fermat_num,
+            congruent_minus_1,
+            result,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct PrimeCountTable {
+    x: String,
+    #[tabled(rename = "π(x)")]
+    pi: String,
+    #[tabled(rename = "x/ln x")]
+    x_over_ln_x: String,
+    #[tabled(rename = "li(x)")]
+    li: String,
+    #[tabled(rename = "(x/ln x - π(x))/π(x)")]
+    x_over_ln_x_error: String,
+    #[tabled(rename = "(li(x) - π(x))/π(x)")]
+    li_error: String,
+}
+
+impl PrimeCountTable {
+    pub fn new(
+        x: String,
+        pi: String,
+        x_over_ln_x: String,
+        li: String,
+        x_over_ln_x_error: String,
+        li_error: String,
+    ) -> Self {
+        Self {
+            x,
+            pi,
+            x_over_ln_x,
+            li,
+            x_over_ln_x_error,
+            li_error,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct ArithmeticTable {
+    n: String,
+    #[tabled(rename = "φ(n)")]
+    phi: String,
+    #[tabled(rename = "μ(n)")]
+    mu: String,
+    #[tabled(rename = "σₖ(n)")]
+    sigma: String,
+    #[tabled(rename = "τ(n)")]
+    tau: String,
+    #[tabled(rename = "λ(n)")]
+    lambda: String,
+    #[tabled(rename = "ω(n)")]
+    omega: String,
+    #[tabled(rename = "Ω(n)")]
+    big_omega: String,
+}
+
+impl ArithmeticTable {
+    pub fn new(n: u64, f: &ArithmeticFunctions) -> Self {
+        Self {
+            n: n.to_string(),
+            phi: f.phi.to_string(),
+            mu: f.mu.to_string(),
+            sigma: f.sigma.to_string(),
+            tau: f.tau.to_string(),
+            lambda: f.lambda.to_string(),
+            omega: f.omega.to_string(),
+            big_omega: f.big_omega.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct GoldbachTable {
+    n: String,
+    partitions: String,
+    #[tabled(rename = "minimal partition")]
+    minimal: String,
+}
+
+impl GoldbachTable {
+    pub fn new(n: String, partitions: String, minimal: String) -> Self {
+        Self {
+            n,
+            partitions,
+            minimal,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct SquaresTable {
+    n: String,
+    #[tabled(rename = "a² + b²")]
+    two: String,
+    #[tabled(rename = "r₂(n)")]
+    r2: String,
+    #[tabled(rename = "a² + b² + c² + d²")]
+    four: String,
+    #[tabled(rename = "r₄(n)")]
+    r4: String,
+}
+
+impl SquaresTable {
+    pub fn new(squares: &SumsOfSquares) -> Self {
+        let sum = |xs: &[BigInt]| {
+            xs.iter()
+                .map(|x| format!("{}²", x))
+                .collect::<Vec<String>>()
+                .join(" + ")
+        };
+        Self {
+            n: squares.n.to_string(),
+            two: squares
+                .two
+                .as_ref()
+                .map_or("-".to_string(), |(a, b)| sum(&[a.clone(), b.clone()])),
+            r2: squares.r2.to_string(),
+            four: sum(&squares.four),
+            r4: squares.r4.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct NthPrimeTable {
+    n: String,
+    #[tabled(rename = "pₙ")]
+    p_n: String,
+}
+
+impl NthPrimeTable {
+    pub fn new(n: String, p_n: String) -> Self {
+        Self { n, p_n }
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct PrimeGapTable {
+    gap: String,
+    p: String,
+    #[tabled(rename = "p + gap")]
+    next: String,
+}
+
+impl PrimeGapTable {
+    pub fn new(gap: String, p: String, next: String) -> Self {
+        Self { gap, p, next }
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct GapHistogramTable {
+    gap: String,
+    count: String,
+    share: String,
+}
+
+impl GapHistogramTable {
+    pub fn new(gap: String, count: String, share: String) -> Self {
+        Self { gap, count, share }
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct GeneratedPrimeTable {
+    #[tabled(rename = "#")]
+    index: String,
+    p: String,
+    bits: String,
+    structure: String,
+}
+
+impl GeneratedPrimeTable {
+    pub fn new(index: String, p: String, bits: String, structure: String) -> Self {
+        Self {
+            index,
+            p,
+            bits,
+            structure,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct AksTable {
+    step: String,
+    check: String,
+    outcome: String,
+    time: String,
+}
+
+impl AksTable {
+    pub fn new(step: String, check: String, outcome: String, time: String) -> Self {
+        Self {
+            step,
+            check,
+            outcome,
+            time,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Tabled, Serialize, Deserialize)]
+pub struct AksFailedTable {
+    n: String,
+    r: String,
+    #[tabled(rename = "ordᵣ(n)")]
+    order: String,
+    a: String,
+}
+
+impl AksFailedTable {
+    pub fn new(n: String, r: String, order: String, a: String) -> Self {
+        Self { n, r, order, a }
+    }
+}
+
 #[derive(Tabled)]
 #[tabled(rename_all = "PascalCase")]
 pub struct Matrix {
@@ -296,6 +636,307 @@ impl PollardsRhoJson {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct PollardsP1Table {
+    stage: String,
+    base: String,
+    primes: String,
+    #[tabled(rename = "a")]
+    a: String,
+    #[tabled(rename = "gcd(a - 1, n)")]
+    gcd: String,
+}
+
+impl PollardsP1Table {
+    pub fn new(stage: String, base: String, primes: String, a: String, gcd: String) -> Self {
+        Self {
+            stage,
+            base,
+            primes,
+            a,
+            gcd,
+        }
+    }
+
+    pub fn stage(&self) -> &str {
+        &self.stage
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct WilliamsPPlus1Table {
+    stage: String,
+    #[tabled(rename = "A")]
+    a: String,
+    primes: String,
+    #[tabled(rename = "V")]
+    v: String,
+    #[tabled(rename = "gcd(V - 2, n)")]
+    gcd: String,
+}
+
+impl WilliamsPPlus1Table {
+    pub fn new(stage: String, a: String, primes: String, v: String, gcd: String) -> Self {
+        Self {
+            stage,
+            a,
+            primes,
+            v,
+            gcd,
+        }
+    }
+
+    pub fn stage(&self) -> &str {
+        &self.stage
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct EcmTable {
+    curve: String,
+    #[tabled(rename = "σ")]
+    sigma: String,
+    stage: String,
+    primes: String,
+    #[tabled(rename = "gcd")]
+    gcd: String,
+}
+
+impl EcmTable {
+    pub fn new(curve: String, sigma: String, stage: String, primes: String, gcd: String) -> Self {
+        Self {
+            curve,
+            sigma,
+            stage,
+            primes,
+            gcd,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct QsFactorBaseTable {
+    p: String,
+    #[tabled(rename = "√n (mod p)")]
+    roots: String,
+    #[tabled(rename = "log₂p")]
+    log_p: String,
+}
+
+impl QsFactorBaseTable {
+    pub fn new(p: String, roots: String, log_p: String) -> Self {
+        Self { p, roots, log_p }
+    }
+
+    pub fn p(&self) -> &str {
+        &self.p
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct QsRelationTable {
+    t: String,
+    #[tabled(rename = "x = t - ⌊√n⌋")]
+    x: String,
+    #[tabled(rename = "y = t² - n")]
+    y: String,
+    factors: String,
+    #[tabled(rename = "exponents mod 2")]
+    vector: String,
+}
+
+impl QsRelationTable {
+    pub fn new(t: String, x: String, y: String, factors: String, vector: String) -> Self {
+        Self {
+            t,
+            x,
+            y,
+            factors,
+            vector,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct QsDependencyTable {
+    dependency: String,
+    #[tabled(rename = "relations")]
+    relations: String,
+    #[tabled(rename = "X = ∏t")]
+    x: String,
+    #[tabled(rename = "Y = √∏y")]
+    y: String,
+    #[tabled(rename = "gcd(X - Y, n)")]
+    gcd: String,
+}
+
+impl QsDependencyTable {
+    pub fn new(dependency: String, relations: String, x: String, y: String, gcd: String) -> Self {
+        Self {
+            dependency,
+            relations,
+            x,
+            y,
+            gcd,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct Gf2DependencyTable {
+    dependency: String,
+    rows: String,
+    lines: String,
+}
+
+impl Gf2DependencyTable {
+    pub fn new(dependency: String, rows: String, lines: String) -> Self {
+        Self {
+            dependency,
+            rows,
+            lines,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct PollardsRhoBrentTable {
+    c: String,
+    r: String,
+    k: String,
+    #[tabled(rename = "x = y(r)")]
+    x: String,
+    y: String,
+    #[tabled(rename = "gcd(∏|x - y|, n)")]
+    gcd: String,
+}
+
+impl PollardsRhoBrentTable {
+    pub fn new(c: String, r: String, k: String, x: String, y: String, gcd: String) -> Self {
+        Self { c, r, k, x, y, gcd }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct FermatFactorTable {
+    step: String,
+    x: String,
+    #[tabled(rename = "x² - kn")]
+    r: String,
+    #[tabled(rename = "y = √(x² - kn)")]
+    y: String,
+    #[tabled(rename = "gcd(x - y, n)")]
+    gcd: String,
+}
+
+impl FermatFactorTable {
+    pub fn new(step: String, x: String, r: String, y: String, gcd: String) -> Self {
+        Self { step, x, r, y, gcd }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct LehmanTable {
+    k: String,
+    x: String,
+    #[tabled(rename = "x² - 4kn")]
+    r: String,
+    #[tabled(rename = "y = √(x² - 4kn)")]
+    y: String,
+    #[tabled(rename = "gcd(x + y, n)")]
+    gcd: String,
+}
+
+impl LehmanTable {
+    pub fn new(k: String, x: String, r: String, y: String, gcd: String) -> Self {
+        Self { k, x, r, y, gcd }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct HartTable {
+    i: String,
+    #[tabled(rename = "s = ⌈√(in)⌉")]
+    s: String,
+    #[tabled(rename = "s² mod n")]
+    m: String,
+    #[tabled(rename = "t = √(s² mod n)")]
+    t: String,
+    #[tabled(rename = "gcd(s - t, n)")]
+    gcd: String,
+}
+
+impl HartTable {
+    pub fn new(i: String, s: String, m: String, t: String, gcd: String) -> Self {
+        Self { i, s, m, t, gcd }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct EulerFactorTable {
+    #[tabled(rename = "a² + b²")]
+    first: String,
+    #[tabled(rename = "c² + d²")]
+    second: String,
+    #[tabled(rename = "ad - bc")]
+    minus: String,
+    #[tabled(rename = "gcd(ad - bc, n)")]
+    gcd_minus: String,
+    #[tabled(rename = "ad + bc")]
+    plus: String,
+    #[tabled(rename = "gcd(ad + bc, n)")]
+    gcd_plus: String,
+}
+
+impl EulerFactorTable {
+    pub fn new(
+        first: String,
+        second: String,
+        minus: String,
+        gcd_minus: String,
+        plus: String,
+        gcd_plus: String,
+    ) -> Self {
+        Self {
+            first,
+            second,
+            minus,
+            gcd_minus,
+            plus,
+            gcd_plus,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Tabled)]
+pub struct SqufofTable {
+    k: String,
+    cycle: String,
+    i: String,
+    #[tabled(rename = "P")]
+    p: String,
+    #[tabled(rename = "Q")]
+    q: String,
+    result: String,
+}
+
+impl SqufofTable {
+    pub fn new(k: String, cycle: String, i: String, p: String, q: String, result: String) -> Self {
+        Self {
+            k,
+            cycle,
+            i,
+            p,
+            q,
+            result,
+        }
+    }
+
+    pub fn cycle(&self) -> &str {
+        &self.cycle
+    }
+}
+
 #[derive(Tabled)]
 #[tabled(rename_all = "PascalCase")]
 pub struct PrimitiveRootsTable {
@@ -0,0 +1,296 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::StdRng;
+use rayon::prelude::*;
+
+use crate::{
+    display::EcmTable,
+    factorisations::trivial_split,
+    sieve::{segmented_sieve, PrimeStream},
+    utils::{generate_random_int_in_range, modular_inv, Gcd},
+};
+
+/// Giant step w = 2.3.5.7 of the ECM stage 2.
+const ECM_WHEEL: u64 = 210;
+
+///
+/// A point (X : Z) of a Montgomery curve By² = x³ + Ax² + x, x = X/Z. Only x is
+/// carried, which fixes P up to sign; the point at infinity is (1 : 0).
+///
+#[derive(Clone, Debug)]
+pub struct MontgomeryPoint {
+    pub x: BigInt,
+    pub z: BigInt,
+}
+
+impl MontgomeryPoint {
+    pub fn infinity() -> Self {
+        Self {
+            x: BigInt::one(),
+            z: BigInt::zero(),
+        }
+    }
+}
+
+///
+/// A Montgomery curve over Z/nZ, kept as a24 = (A + 2)/4 which is all the x-only
+/// formulas need
+///
+#[derive(Clone, Debug)]
+pub struct MontgomeryCurve {
+    n: BigInt,
+    a24: BigInt,
+}
+
+impl MontgomeryCurve {
+    ///
+    /// Suyama's parametrisation: u = σ² - 5, v = 4σ, P = (u³ : v³) and
+    /// A + 2 = (v - u)³(3u + v)/(4u³v). The group order is divisible by 12 for every p.
+    /// Err(gcd(16u³v, n)) when the denominator is not invertible mod n.
+    ///
+    pub fn suyama(sigma: &BigInt, n: &BigInt) -> Result<(Self, MontgomeryPoint), BigInt> {
+        let u = (sigma * sigma - 5u32).mod_floor(n);
+        let v = (sigma * 4u32).mod_floor(n);
+        let u_3 = u.modpow(&BigInt::from(3u64), n);
+        let v_3 = v.modpow(&BigInt::from(3u64), n);
+
+        let denominator = (&u_3 * &v * 16u32).mod_floor(n);
+        let g = denominator.gcd_euclid(n);
+        if !g.is_one() {
+            return Err(g);
+        }
+        let numerator = (&v - &u).modpow(&BigInt::from(3u64), n) * (&u * 3u32 + &v);
+        let a24 = (numerator * modular_inv(&denominator, n)).mod_floor(n);
+        Ok((
+            Self { n: n.clone(), a24 },
+            MontgomeryPoint { x: u_3, z: v_3 },
+        ))
+    }
+
+    ///
+    /// [2]P: X₂ = (X + Z)²(X - Z)², Z₂ = 4XZ((X - Z)² + a24.4XZ)
+    ///
+    pub fn double(&self, p: &MontgomeryPoint) -> MontgomeryPoint {
+        let n = &self.n;
+        let sum = (&p.x + &p.z).pow(2) % n;
+        let diff = (&p.x - &p.z).pow(2) % n;
+        let t = &sum - &diff;
+        MontgomeryPoint {
+            x: (&sum * &diff).mod_floor(n),
+            z: (&t * (&diff + &self.a24 * &t)).mod_floor(n),
+        }
+    }
+
+    ///
+    /// P + Q from P, Q and P - Q: X = Z₋((X_P - Z_P)(X_Q + Z_Q) + (X_P + Z_P)(X_Q - Z_Q))²,
+    /// Z = X₋((X_P - Z_P)(X_Q + Z_Q) - (X_P + Z_P)(X_Q - Z_Q))²
+    ///
+    pub fn add(
+        &self,
+        p: &MontgomeryPoint,
+        q: &MontgomeryPoint,
+        diff: &MontgomeryPoint,
+    ) -> MontgomeryPoint {
+        let n = &self.n;
+        let u = (&p.x - &p.z) * (&q.x + &q.z) % n;
+        let v = (&p.x + &p.z) * (&q.x - &q.z) % n;
+        MontgomeryPoint {
+            x: (&diff.z * (&u + &v).pow(2)).mod_floor(n),
+            z: (&diff.x * (&u - &v).pow(2)).mod_floor(n),
+        }
+    }
+
+    ///
+    /// [k]P by the Montgomery ladder, which keeps R₁ - R₀ = P at every step
+    ///
+    pub fn multiply(&self, p: &MontgomeryPoint, k: u64) -> MontgomeryPoint {
+        if k == 0 {
+            return MontgomeryPoint::infinity();
+        }
+        let (mut r0, mut r1) = (p.clone(), self.double(p));
+        for bit in (0..u64::BITS - 1 - k.leading_zeros()).rev() {
+            if k >> bit & 1 == 1 {
+                r0 = self.add(&r1, &r0, p);
+                r1 = self.double(&r1);
+            } else {
+                r1 = self.add(&r0, &r1, p);
+                r0 = self.double(&r0);
+            }
+        }
+        r0
+    }
+}
+
+///
+/// One ECM curve: the factor found, if any, and a row per stage
+///
+fn ecm_curve(
+    n: &BigInt,
+    curve: usize,
+    sigma: &BigInt,
+    b1: u64,
+    b2: u64,
+) -> (Option<BigInt>, Vec<EcmTable>) {
+    let mut table_data: Vec<EcmTable> = Vec::new();
+    let row = |stage: &str, primes: String, g: &BigInt| {
+        EcmTable::new(
+            curve.to_string(),
+            sigma.to_string(),
+            stage.to_string(),
+            primes,
+            g.to_string(),
+        )
+    };
+    let found = |g: &BigInt| !g.is_one() && g != n;
+
+    let (e, mut q) = match MontgomeryCurve::suyama(sigma, n) {
+        Ok(curve) => curve,
+        Err(g) => {
+            table_data.push(row("σ", "-".to_string(), &g));
+            return (Some(g).filter(found), table_data);
+        }
+    };
+
+    // stage 1
+    for p in segmented_sieve(2, b1) {
+        let mut p_e = p;
+        while p_e <= b1 / p {
+            p_e *= p;
+        }
+        q = e.multiply(&q, p_e);
+    }
+    let g = q.z.gcd_euclid(n);
+    table_data.push(row("1", format!("2 - {}", b1), &g));
+    if !g.is_one() || b2 <= b1 {
+        return (Some(g).filter(found), table_data);
+    }
+
+    // stage 2, a prime p = mw ± j with [p]Q = O gives [mw]Q = ∓[j]Q, so x([mw]Q) = x([j]Q)
+    let w = ECM_WHEEL;
+    let mut baby = vec![MontgomeryPoint::infinity(), q.clone(), e.double(&q)];
+    for j in 3..=(w / 2) as usize {
+        let next = e.add(&baby[j - 1], &q, &baby[j - 2]);
+        baby.push(next);
+    }
+    let q_w = e.multiply(&q, w);
+    // [mw]Q and [(m - 1)w]Q, [-w]Q has the x of [w]Q
+    let (mut m, mut giant, mut giant_prev) = (0u64, MontgomeryPoint::infinity(), q_w.clone());
+    let mut product = BigInt::one();
+    for p in PrimeStream::new(b1 + 1, b2) {
+        let (p_m, j) = match p % w {
+            r if r <= w / 2 => (p / w, r),
+            r => (p / w + 1, w - r),
+        };
+        while m < p_m {
+            // [2w]Q has the difference O from [w]Q + [w]Q, the formula needs a doubling
+            let next = match m {
+                1 => e.double(&q_w),
+                _ => e.add(&giant, &q_w, &giant_prev),
+            };
+            giant_prev = std::mem::replace(&mut giant, next);
+            m += 1;
+        }
+        let b = &baby[j as usize];
+        product = (product * (&giant.x * &b.z - &b.x * &giant.z)).mod_floor(n);
+    }
+    let g = product.gcd_euclid(n);
+    table_data.push(row("2", format!("{} - {}", b1 + 1, b2), &g));
+    (Some(g).filter(found), table_data)
+}
+
+///
+/// Lenstra's elliptic curve method. Returns a nontrivial factor of a composite n and
+/// one row per curve and stage.
+///
+/// Pollard's p - 1 needs p - 1 smooth, ECM needs the order of a random curve mod p to
+/// be smooth, and the orders of Suyama's curves are spread over [p + 1 - 2√p, p + 1 + 2√p].
+/// Stage 1 multiplies the starting point by every prime power <= B1, stage 2 covers
+/// one further prime <= B2 with baby steps [j]Q and giant steps [mw]Q. The curves are
+/// drawn in order from rng and run in parallel, a round per available thread.
+///
+pub fn ecm(
+    n: &BigInt,
+    b1: u64,
+    b2: u64,
+    curves: usize,
+    rng: &mut StdRng,
+) -> (Option<BigInt>, Vec<EcmTable>) {
+    let mut table_data: Vec<EcmTable> = Vec::new();
+    if let Some(factor) = trivial_split(n) {
+        return (factor, table_data);
+    }
+
+    let sigmas = (0..curves)
+        .map(|_| generate_random_int_in_range(rng, &BigInt::from(6u64), &(n - 1u32)))
+        .collect::<Vec<BigInt>>();
+    let round = rayon::current_num_threads();
+    for (r, sigmas) in sigmas.chunks(round).enumerate() {
+        let results = sigmas
+            .par_iter()
+            .enumerate()
+            .map(|(i, sigma)| ecm_curve(n, r * round + i + 1, sigma, b1, b2))
+            .collect::<Vec<(Option<BigInt>, Vec<EcmTable>)>>();
+
+        let mut factor = None;
+        for (d, rows) in results {
+            table_data.extend(rows);
+            if d.is_some() {
+                factor = d;
+                break;
+            }
+        }
+        if factor.is_some() {
+            return (factor, table_data);
+        }
+    }
+    (None, table_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::seeded_rng;
+
+    #[test]
+    fn test_montgomery_ladder() {
+        let n = BigInt::from(1000000007u64);
+        let (e, p) = MontgomeryCurve::suyama(&BigInt::from(11u64), &n).unwrap();
+        let same_x = |p: &MontgomeryPoint, q: &MontgomeryPoint| {
+            (&p.x * &q.z - &q.x * &p.z).mod_floor(&n).is_zero()
+        };
+
+        let p2 = e.double(&p);
+        let p3 = e.add(&p2, &p, &p);
+        assert!(same_x(&e.multiply(&p, 3), &p3));
+        assert!(same_x(&e.multiply(&p, 6), &e.double(&p3)));
+        let p5 = e.add(&p3, &p2, &p);
+        assert!(same_x(&e.multiply(&p, 5), &p5));
+        assert!(same_x(
+            &e.multiply(&e.multiply(&p, 12), 35),
+            &e.multiply(&p, 420)
+        ));
+    }
+
+    #[test]
+    fn test_ecm() {
+        let (_, mut rng) = seeded_rng(Some(3));
+        // 10¹² + 39 x (2⁸⁹ - 1)
+        let p = BigInt::from(1000000000039u64);
+        let n = &p * (BigInt::from(2u64).pow(89) - 1u32);
+        let (factor, table_data) = ecm(&n, 2000, 200000, 100, &mut rng);
+        assert_eq!(factor, Some(p));
+        assert!(!table_data.is_empty());
+
+        // σ = 8 mod 10007 gives a curve of order 2³ x 3 x 409, 409 is left to stage 2
+        let n = BigInt::from(10007u64) * (BigInt::from(2u64).pow(89) - 1u32);
+        let sigma = BigInt::from(8u64);
+        let (factor, table_data) = ecm_curve(&n, 1, &sigma, 20, 1000);
+        assert_eq!(factor, Some(BigInt::from(10007u64)));
+        assert_eq!(table_data.len(), 2);
+        assert_eq!(ecm_curve(&n, 1, &sigma, 20, 400).0, None);
+
+        let prime = BigInt::from(2u64).pow(89) - 1u32;
+        assert_eq!(ecm(&prime, 2000, 200000, 10, &mut rng).0, None);
+    }
+}
@@ -1,63 +1,1017 @@
+use std::collections::HashMap;
+
 use num_bigint::BigInt;
-use num_traits::One;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use rand::rngs::StdRng;
 
 use crate::{
-    primality::miller_rabin_primality,
-    utils::{modular_pow, Gcd},
+    display::{
+        FermatFactorTable, HartTable, LehmanTable, PollardsP1Table, PollardsRhoBrentTable,
+        SqufofTable, WilliamsPPlus1Table,
+    },
+    elliptic_curves::ecm,
+    primality::baillie_psw,
+    prime_factors::PrimeFactors,
+    sieve::{segmented_sieve, PrimeStream},
+    siqs::siqs,
+    utils::{generate_random_int_in_range, modular_pow, Gcd},
+};
+
+/// Primes covered by each gcd in both stages of Pollard's p - 1.
+const P_1_BATCH: usize = 64;
+/// Bases tried by Pollard's p - 1 when every prime factor comes out at once.
+const P_1_BASES: usize = 10;
+/// Largest automatic stage 1 bound of Pollard's p - 1.
+const P_1_MAX_B1: u64 = 1_000_000;
+/// Starting values A tried by Williams' p + 1, each picks p - 1 or p + 1 at random.
+const P_PLUS_1_SEEDS: usize = 3;
+/// Giant step w = 2.3.5.7 of the stage 2 of Williams' p + 1.
+const P_PLUS_1_WHEEL: u64 = 210;
+
+/// Products of |x - y| collected before each gcd in Brent's variant of Pollard's rho.
+const RHO_BATCH: u64 = 128;
+/// Values of c tried by Pollard's rho before it gives up on n.
+const RHO_ATTEMPTS: usize = 20;
+/// The factoriser strips prime factors below this bound by trial division first.
+const TRIAL_DIVISION_BOUND: u64 = 1 << 16;
+/// Attempts of the factoriser at a composite part before it is left unfactored.
+const SPLIT_ATTEMPTS: usize = 3;
+/// Multipliers k of SQUFOF, the squarefree products of 3, 5, 7 and 11.
+const SQUFOF_MULTIPLIERS: [u64; 16] = [
+    1, 3, 5, 7, 11, 15, 21, 33, 35, 55, 77, 105, 165, 231, 385, 1155,
+];
+/// Rows kept from each cycle of the SQUFOF trace, the last step is always added.
+const SQUFOF_ROWS: u64 = 10;
+/// Cofactors up to this many bits try SQUFOF before Pollard's rho.
+const SQUFOF_MAX_BITS: u64 = 62;
+/// Cofactors up to this many bits are split by Pollard's rho, larger ones by ECM.
+const RHO_MAX_BITS: u64 = 64;
+/// Cofactors up to this many bits (60 digits, seconds of sieving) go to SIQS when the
+/// first ECM levels find nothing.
+const SIQS_MAX_BITS: u64 = 200;
+/// ECM levels tried before SIQS, enough for the factors of up to 20 digits.
+const ECM_LEVELS_BEFORE_SIQS: usize = 2;
+/// Rows kept of the step tables of Fermat, Lehman and Hart, the last step is always added.
+const SQUARE_SEARCH_ROWS: u64 = 30;
+/// Bit mask of the squares mod 64, most non-squares are ruled out without a square root.
+const SQUARES_MOD_64: u64 = {
+    let (mut mask, mut x) = (0u64, 0u64);
+    while x < 64 {
+        mask |= 1 << (x * x % 64);
+        x += 1;
+    }
+    mask
 };
+/// (B1, curves) of the factoriser's ECM levels, for factors of 15, 20, 25, 30 and 35 digits.
+const ECM_LEVELS: [(u64, usize); 5] = [
+    (2000, 25),
+    (11000, 90),
+    (50000, 300),
+    (250000, 700),
+    (1000000, 1800),
+];
+
+///
+/// Default bounds for Pollard's p - 1: B1 = n¹ᐟ⁶ kept within [10³, 10⁶] and B2 = 100.B1
+///
+pub fn p_1_bounds(n: &BigInt) -> (u64, u64) {
+    let b1 = n
+        .nth_root(6)
+        .to_u64()
+        .map_or(P_1_MAX_B1, |b1| b1.clamp(1000, P_1_MAX_B1));
+    (b1, 100 * b1)
+}
+
+///
+/// Pollard's p - 1 method with the standard stage 2. Returns n = p.q and one row per gcd.
+///
+/// Stage 1: a = base^M (mod n), M the product of the largest powers qᵉ <= B1 of the
+/// primes q <= B1. If p - 1 | M for a prime p | n then aᴹ ≡ 1 (mod p) and
+/// gcd(a - 1, n) picks up p.
+/// Stage 2: p - 1 = M'.q with M' | M and one prime B1 < q <= B2. With a fixed, aᵠ is
+/// stepped from prime to prime by multiplying with a^(gap), the gaps are few and
+/// their powers are cached. The product of the aᵠ - 1 is reduced by one gcd per batch.
+///
+/// Each gcd covers 64 primes. When it equals n the batch is replayed one prime power at a
+/// time, and if the factors still come out together the next base is tried.
+///
+pub fn pollards_p_1(
+    n: &BigInt,
+    base: &BigInt,
+    b1: u64,
+    b2: u64,
+) -> (Option<(BigInt, BigInt)>, Vec<PollardsP1Table>) {
+    let mut table_data: Vec<PollardsP1Table> = Vec::new();
+    let split = |g: BigInt| Some((n / &g, g));
+    if let Some(factor) = trivial_split(n) {
+        return (factor.and_then(split), table_data);
+    }
+
+    let mut base = base.clone();
+    for _ in 0..P_1_BASES {
+        let g = base.mod_floor(n).gcd_euclid(n);
+        // a base ≡ 0 (mod n) makes every aᵏ - 1 ≡ -1, try the next one
+        if &g == n {
+            base += 1u32;
+            continue;
+        }
+        if !g.is_one() {
+            return (split(g), table_data);
+        }
+
+        // stage 1
+        let mut a = base.clone();
+        let mut failed = false;
+        let primes = segmented_sieve(2, b1);
+        for batch in primes.chunks(P_1_BATCH) {
+            let a_start = a.clone();
+            for q in batch {
+                a = modular_pow(&a, &BigInt::from(prime_power_below(*q, b1)), n);
+            }
+            let g = (&a - 1u32).gcd_euclid(n);
+            table_data.push(PollardsP1Table::new(
+                "1".to_string(),
+                base.to_string(),
+                format!("{} - {}", batch[0], batch[batch.len() - 1]),
+                a.to_string(),
+                g.to_string(),
+            ));
+            if g.is_one() {
+                continue;
+            }
+            if &g != n {
+                return (split(g), table_data);
+            }
+
+            // the batch finished off every prime factor at once, step through it
+            a = a_start;
+            for q in batch {
+                for _ in 0..prime_power_below(*q, b1).ilog(*q) {
+                    a = modular_pow(&a, &BigInt::from(*q), n);
+                    let g = (&a - 1u32).gcd_euclid(n);
+                    if g.is_one() {
+                        continue;
+                    }
+                    table_data.push(PollardsP1Table::new(
+                        "1 (backtrack)".to_string(),
+                        base.to_string(),
+                        q.to_string(),
+                        a.to_string(),
+                        g.to_string(),
+                    ));
+                    if &g != n {
+                        return (split(g), table_data);
+                    }
+                    failed = true;
+                    break;
+                }
+                if failed {
+                    break;
+                }
+            }
+            break;
+        }
+
+        // stage 2
+        if !failed {
+            let mut gap_powers: HashMap<u64, BigInt> = HashMap::new();
+            let mut primes = PrimeStream::new(b1 + 1, b2);
+            let (mut q_prev, mut a_q) = (0u64, BigInt::one());
+            loop {
+                let mut batch: Vec<BigInt> = Vec::with_capacity(P_1_BATCH);
+                let mut q_first = None;
+                for q in primes.by_ref().take(P_1_BATCH) {
+                    let step = gap_powers
+                        .entry(q - q_prev)
+                        .or_insert_with(|| modular_pow(&a, &BigInt::from(q - q_prev), n));
+                    a_q = &a_q * &*step % n;
+                    q_first.get_or_insert(q);
+                    q_prev = q;
+                    batch.push(&a_q - 1u32);
+                }
+                let Some(q_first) = q_first else {
+                    break;
+                };
+
+                let product = batch.iter().fold(BigInt::one(), |acc, x| acc * x % n);
+                let g = product.gcd_euclid(n);
+                table_data.push(PollardsP1Table::new(
+                    "2".to_string(),
+                    base.to_string(),
+                    format!("{} - {}", q_first, q_prev),
+                    a_q.to_string(),
+                    g.to_string(),
+                ));
+                if g.is_one() {
+                    continue;
+                }
+                if &g != n {
+                    return (split(g), table_data);
+                }
+                // one aᵠ - 1 at a time
+                match batch.iter().map(|x| x.gcd_euclid(n)).find(|g| !g.is_one()) {
+                    Some(g) if &g != n => return (split(g), table_data),
+                    _ => {
+                        failed = true;
+                        break;
+                    }
+                }
+            }
+        }
+        if !failed {
+            // p - 1 is not B1, B2 smooth for any p | n, another base will not change that
+            return (None, table_data);
+        }
+        base += 1u32;
+    }
+    (None, table_data)
+}
+
+///
+/// The largest power qᵉ <= bound of the prime q <= bound
+///
+fn prime_power_below(q: u64, bound: u64) -> u64 {
+    let mut q_e = q;
+    while q_e <= bound / q {
+        q_e *= q;
+    }
+    q_e
+}
+
+///
+/// Williams' p + 1 method. Returns n = p.q and one row per gcd.
+///
+/// With V₀ = 2, V₁ = A and Vₖ₊₁ = A.Vₖ - Vₖ₋₁, Vₖ = αᵏ + α⁻ᵏ for a root α of
+/// x² - Ax + 1. Mod a prime p, α lies in a group of order p - (D/p), D = A² - 4.
+/// Stage 1: V = V_M(A) (mod n), M as in Pollard's p - 1. If p - (D/p) | M then αᴹ ≡ 1
+/// and gcd(V - 2, n) picks up p.
+/// Stage 2: p - (D/p) = M'.q with one prime B1 < q <= B2. Writing q = mw ± j with
+/// w = 210 and j <= w/2, αᵠ ≡ 1 gives V_(mw) ≡ V_j (mod p). The giant steps V_(mw) and
+/// the baby steps V_j come from the recurrence and one gcd is taken per 64 primes of
+/// the product of V_(mw) - V_j.
+///
+/// (D/p) is unknown, so when a run finds nothing the next A is tried; a run with
+/// (D/p) = 1 is Pollard's p - 1 in disguise.
+///
+pub fn williams_p_plus_1(
+    n: &BigInt,
+    a: &BigInt,
+    b1: u64,
+    b2: u64,
+) -> (Option<(BigInt, BigInt)>, Vec<WilliamsPPlus1Table>) {
+    let mut table_data: Vec<WilliamsPPlus1Table> = Vec::new();
+    let split = |g: BigInt| Some((n / &g, g));
+    if let Some(factor) = trivial_split(n) {
+        return (factor.and_then(split), table_data);
+    }
 
-pub fn pollards_p_1(n: &BigInt, base: &BigInt) {
-    if miller_rabin_primality(n) {
-        println!("{} is a prime", &n);
-        return;
-    }
-    // This list need to be generated dynamically. Static it is for now.
-    let prime_powers: Vec<BigInt> = vec![
-        BigInt::from(2u64),
-        BigInt::from(3u64),
-        BigInt::from(2u64),
-        BigInt::from(5u64),
-        BigInt::from(7u64),
-        BigInt::from(2u64),
-        BigInt::from(3u64),
-        BigInt::from(11u64),
-        BigInt::from(13u64),
-        BigInt::from(2u64),
-        BigInt::from(17u64),
-        BigInt::from(19u64),
-        BigInt::from(23u64),
-        BigInt::from(5u64),
-        BigInt::from(3u64),
-        BigInt::from(29u64),
-        BigInt::from(31u64),
-        BigInt::from(2u64),
-        BigInt::from(37u64),
-        BigInt::from(41u64),
-        BigInt::from(43u64),
-        BigInt::from(47u64),
-        BigInt::from(49u64),
-        BigInt::from(53u64),
-        BigInt::from(59u64),
-        BigInt::from(61u64),
-        BigInt::from(2u64),
-        BigInt::from(67u64),
-        BigInt::from(71u64),
-        BigInt::from(73u64),
-        BigInt::from(79u64),
-        BigInt::from(3u64),
-        BigInt::from(83u64),
-        BigInt::from(89u64),
-        BigInt::from(97u64),
-    ];
-
-    let mut b = base.clone();
-    for num in prime_powers.iter() {
-        b = modular_pow(&b, num, n);
-        let gcd = n.gcd_euclid(&(&b - BigInt::one()));
-        println!("{:>10} {:>10} {:>10}", num, &b, gcd);
-        if &gcd > &BigInt::one() && &gcd < n {
-            println!("{} = {} x {}", n, &gcd, n / &gcd);
+    let mut a = a.clone();
+    for _ in 0..P_PLUS_1_SEEDS {
+        let g = (&a * &a - 4u32).mod_floor(n).gcd_euclid(n);
+        if !g.is_one() && !g.is_zero() && &g != n {
+            return (split(g), table_data);
+        }
+
+        // stage 1
+        let mut v = a.mod_floor(n);
+        let mut failed = false;
+        let primes = segmented_sieve(2, b1);
+        for batch in primes.chunks(P_1_BATCH) {
+            let v_start = v.clone();
+            for q in batch {
+                v = lucas_v(&v, prime_power_below(*q, b1), n);
+            }
+            let g = (&v - 2u32).mod_floor(n).gcd_euclid(n);
+            table_data.push(WilliamsPPlus1Table::new(
+                "1".to_string(),
+                a.to_string(),
+                format!("{} - {}", batch[0], batch[batch.len() - 1]),
+                v.to_string(),
+                g.to_string(),
+            ));
+            if g.is_one() {
+                continue;
+            }
+            if &g != n {
+                return (split(g), table_data);
+            }
+
+            // the batch finished off every prime factor at once, step through it
+            v = v_start;
+            'replay: for q in batch {
+                for _ in 0..prime_power_below(*q, b1).ilog(*q) {
+                    v = lucas_v(&v, *q, n);
+                    let g = (&v - 2u32).mod_floor(n).gcd_euclid(n);
+                    if g.is_one() {
+                        continue;
+                    }
+                    table_data.push(WilliamsPPlus1Table::new(
+                        "1 (backtrack)".to_string(),
+                        a.to_string(),
+                        q.to_string(),
+                        v.to_string(),
+                        g.to_string(),
+                    ));
+                    if &g != n {
+                        return (split(g), table_data);
+                    }
+                    failed = true;
+                    break 'replay;
+                }
+            }
             break;
         }
+
+        // stage 2
+        if !failed {
+            let w = P_PLUS_1_WHEEL;
+            let mut baby = vec![BigInt::from(2u64), v.clone()];
+            for j in 2..=(w / 2) as usize {
+                let v_j = (&baby[j - 1] * &v - &baby[j - 2]).mod_floor(n);
+                baby.push(v_j);
+            }
+            let v_w = lucas_v(&v, w, n);
+            // V_(mw) and V_((m - 1)w), V₋ₖ = Vₖ
+            let (mut m, mut giant, mut giant_prev) = (0u64, BigInt::from(2u64), v_w.clone());
+
+            let mut primes = PrimeStream::new(b1 + 1, b2);
+            loop {
+                let mut batch: Vec<BigInt> = Vec::with_capacity(P_1_BATCH);
+                let (mut q_first, mut q_last) = (None, 0);
+                for q in primes.by_ref().take(P_1_BATCH) {
+                    let (q_m, j) = match q % w {
+                        r if r <= w / 2 => (q / w, r),
+                        r => (q / w + 1, w - r),
+                    };
+                    while m < q_m {
+                        let next = (&giant * &v_w - &giant_prev).mod_floor(n);
+                        giant_prev = std::mem::replace(&mut giant, next);
+                        m += 1;
+                    }
+                    q_first.get_or_insert(q);
+                    q_last = q;
+                    batch.push((&giant - &baby[j as usize]).mod_floor(n));
+                }
+                let Some(q_first) = q_first else {
+                    break;
+                };
+
+                let product = batch.iter().fold(BigInt::one(), |acc, x| acc * x % n);
+                let g = product.gcd_euclid(n);
+                table_data.push(WilliamsPPlus1Table::new(
+                    "2".to_string(),
+                    a.to_string(),
+                    format!("{} - {}", q_first, q_last),
+                    giant.to_string(),
+                    g.to_string(),
+                ));
+                if g.is_one() {
+                    continue;
+                }
+                if &g != n {
+                    return (split(g), table_data);
+                }
+                // one V_(mw) - V_j at a time
+                match batch.iter().map(|x| x.gcd_euclid(n)).find(|g| !g.is_one()) {
+                    Some(g) if &g != n => return (split(g), table_data),
+                    _ => break,
+                }
+            }
+        }
+        a += 1u32;
+    }
+    (None, table_data)
+}
+
+///
+/// Vₖ(V) (mod n) by the Lucas chain V₂ₖ = Vₖ² - 2, V₂ₖ₊₁ = VₖVₖ₊₁ - V, which also
+/// gives Vₖ(Vₘ(A)) = Vₖₘ(A)
+///
+fn lucas_v(v: &BigInt, k: u64, n: &BigInt) -> BigInt {
+    let (mut x, mut y) = (BigInt::from(2u64), v.clone());
+    for bit in (0..u64::BITS - k.leading_zeros()).rev() {
+        if k >> bit & 1 == 1 {
+            x = (&x * &y - v).mod_floor(n);
+            y = (&y * &y - 2u32).mod_floor(n);
+        } else {
+            y = (&x * &y - v).mod_floor(n);
+            x = (&x * &x - 2u32).mod_floor(n);
+        }
+    }
+    x
+}
+
+///
+/// The check every splitting method starts with: Some(None) when there is nothing to
+/// split, n < 4 or a prime, Some(Some(2)) for an even n and None when n is left to the
+/// method.
+///
+pub fn trivial_split(n: &BigInt) -> Option<Option<BigInt>> {
+    if n < &BigInt::from(4u64) || baillie_psw(n) {
+        return Some(None);
+    }
+    if n.is_even() {
+        return Some(Some(BigInt::from(2u64)));
+    }
+    None
+}
+
+///
+/// Pollard's rho with Brent's cycle detection. Returns a nontrivial factor of a
+/// composite n and one row per gcd.
+///
+/// The sequence yᵢ₊₁ = yᵢ² + c (mod n) is eventually periodic mod every prime p | n,
+/// after about √p steps. Brent keeps x fixed at y_(r) while y runs through
+/// y_(r+1), ..., y_(2r), then doubles r, which finds the cycle with one f(y) per step.
+/// The differences |x - y| are multiplied together mod n and a gcd is taken only every
+/// 128 steps. When the batch overshoots and the gcd is n, the last batch is replayed
+/// one step at a time; if that still gives n a new c is drawn.
+///
+pub fn pollards_rho_brent(
+    n: &BigInt,
+    rng: &mut StdRng,
+) -> (Option<BigInt>, Vec<PollardsRhoBrentTable>) {
+    let mut table_data: Vec<PollardsRhoBrentTable> = Vec::new();
+    if let Some(factor) = trivial_split(n) {
+        return (factor, table_data);
+    }
+
+    for _ in 0..RHO_ATTEMPTS {
+        let c = generate_random_int_in_range(rng, &BigInt::one(), &(n - 2u32));
+        let f = |y: &BigInt| (y * y + &c) % n;
+        let mut y = generate_random_int_in_range(rng, &BigInt::zero(), n);
+        let (mut x, mut ys) = (y.clone(), y.clone());
+        let (mut r, mut q, mut g) = (1u64, BigInt::one(), BigInt::one());
+
+        while g.is_one() {
+            x = y.clone();
+            for _ in 0..r {
+                y = f(&y);
+            }
+            let mut k = 0;
+            while k < r && g.is_one() {
+                ys = y.clone();
+                for _ in 0..std::cmp::min(RHO_BATCH, r - k) {
+                    y = f(&y);
+                    q = q * (&x - &y).abs() % n;
+                }
+                g = q.gcd_euclid(n);
+                k += RHO_BATCH;
+                table_data.push(PollardsRhoBrentTable::new(
+                    c.to_string(),
+                    r.to_string(),
+                    std::cmp::min(k, r).to_string(),
+                    x.to_string(),
+                    y.to_string(),
+                    g.to_string(),
+                ));
+            }
+            r *= 2;
+        }
+
+        if &g == n {
+            // the batch went past the cycle, step through it again from ys
+            loop {
+                ys = f(&ys);
+                g = (&x - &ys).abs().gcd_euclid(n);
+                if !g.is_one() {
+                    break;
+                }
+            }
+            table_data.push(PollardsRhoBrentTable::new(
+                c.to_string(),
+                "backtrack".to_string(),
+                "-".to_string(),
+                x.to_string(),
+                ys.to_string(),
+                g.to_string(),
+            ));
+        }
+        if &g != n {
+            return (Some(g), table_data);
+        }
+    }
+    (None, table_data)
+}
+
+///
+/// √r when r >= 0 is a perfect square
+///
+fn exact_sqrt(r: &BigInt) -> Option<BigInt> {
+    let low = (r % 64u32).to_u64()?;
+    if r.is_negative() || SQUARES_MOD_64 >> low & 1 == 0 {
+        return None;
+    }
+    let y = r.sqrt();
+    (&y * &y == *r).then_some(y)
+}
+
+///
+/// ⌈√n⌉
+///
+fn ceil_sqrt(n: &BigInt) -> BigInt {
+    let x = n.sqrt();
+    if &(&x * &x) < n {
+        x + 1u32
+    } else {
+        x
+    }
+}
+
+///
+/// Fermat's method on kn: x runs up from ⌈√kn⌉ until x² - kn = y² is a square, then
+/// kn = (x - y)(x + y) and gcd(x - y, n) is a factor of n unless it only took a factor of k.
+/// For n = pq it takes about (p + q)/2 - √n steps, immediate when p and q are close, which
+/// is why RSA primes must not be. A multiplier k with q/p ≈ k/l brings kln's factors close.
+/// Returns the factor and the first steps, the last one and every square.
+///
+pub fn fermat(n: &BigInt, k: u64, max_steps: u64) -> (Option<BigInt>, Vec<FermatFactorTable>) {
+    let mut table_data: Vec<FermatFactorTable> = Vec::new();
+    if let Some(factor) = trivial_split(n) {
+        return (factor, table_data);
+    }
+
+    let kn = n * k;
+    let x_0 = ceil_sqrt(&kn);
+    for step in 1..=max_steps {
+        let x = &x_0 + step - 1u32;
+        let r = &x * &x - &kn;
+        let y = exact_sqrt(&r);
+        let g = y.as_ref().map(|y| (&x - y).gcd_euclid(n));
+        let found = g.as_ref().is_some_and(|g| !g.is_one() && g != n);
+        if step <= SQUARE_SEARCH_ROWS || y.is_some() || step == max_steps {
+            table_data.push(FermatFactorTable::new(
+                step.to_string(),
+                x.to_string(),
+                r.to_string(),
+                y.map_or("-".to_string(), |y| y.to_string()),
+                g.as_ref().map_or("-".to_string(), |g| g.to_string()),
+            ));
+        }
+        if found {
+            return (g, table_data);
+        }
+    }
+    (None, table_data)
+}
+
+///
+/// Lehman's method, O(n¹ᐟ³). Trial division up to n¹ᐟ³ leaves n = pq with both factors
+/// above n¹ᐟ³, and then some k <= n¹ᐟ³ has 4kn = x² - y² with
+/// √4kn <= x <= √4kn + n¹ᐟ⁶/(4√k), gcd(x + y, n) giving the factor. Returns the factor
+/// and the trial division, the first steps, the last one and every square.
+///
+pub fn lehman(n: &BigInt) -> (Option<BigInt>, Vec<LehmanTable>) {
+    let mut table_data: Vec<LehmanTable> = Vec::new();
+    if let Some(factor) = trivial_split(n) {
+        return (factor, table_data);
+    }
+
+    let b = n.cbrt().to_u64().expect("n¹ᐟ³ below 2⁶⁴");
+    let p = segmented_sieve(3, b + 1)
+        .into_iter()
+        .find(|&p| (n % p).is_zero());
+    table_data.push(LehmanTable::new(
+        "-".to_string(),
+        format!("p <= {}", b),
+        "-".to_string(),
+        "-".to_string(),
+        p.map_or("1".to_string(), |p| p.to_string()),
+    ));
+    if let Some(p) = p {
+        return (Some(BigInt::from(p)), table_data);
+    }
+
+    let n_6 = n.to_f64().expect("finite").powf(1.0 / 6.0);
+    let mut steps = 0;
+    for k in 1..=b.max(1) {
+        let four_kn = n * (4 * k);
+        let x_min = ceil_sqrt(&four_kn);
+        let x_max = four_kn.sqrt() + (n_6 / (4.0 * (k as f64).sqrt())).ceil() as u64;
+        let mut x = x_min;
+        while x <= x_max {
+            steps += 1;
+            let r = &x * &x - &four_kn;
+            let y = exact_sqrt(&r);
+            let g = y.as_ref().map(|y| (&x + y).gcd_euclid(n));
+            let found = g.as_ref().is_some_and(|g| !g.is_one() && g != n);
+            if steps <= SQUARE_SEARCH_ROWS || y.is_some() || (k == b && x == x_max) {
+                table_data.push(LehmanTable::new(
+                    k.to_string(),
+                    x.to_string(),
+                    r.to_string(),
+                    y.map_or("-".to_string(), |y| y.to_string()),
+                    g.as_ref().map_or("-".to_string(), |g| g.to_string()),
+                ));
+            }
+            if found {
+                return (g, table_data);
+            }
+            x += 1u32;
+        }
+    }
+    (None, table_data)
+}
+
+///
+/// Hart's one line factoring: s = ⌈√(in)⌉ for i = 1, 2, ... until s² mod n = t² is a
+/// square, then s² ≡ t² (mod n) and gcd(s - t, n) is a factor. Like Fermat it is
+/// immediate when n = pq with p and q close, or with p/q close to a ratio of small numbers.
+/// Returns the factor and the first steps, the last one and every square.
+///
+pub fn hart_one_line(n: &BigInt, max_steps: u64) -> (Option<BigInt>, Vec<HartTable>) {
+    let mut table_data: Vec<HartTable> = Vec::new();
+    if let Some(factor) = trivial_split(n) {
+        return (factor, table_data);
+    }
+
+    for i in 1..=max_steps {
+        let s = ceil_sqrt(&(n * i));
+        let m = (&s * &s) % n;
+        let t = exact_sqrt(&m);
+        let g = t.as_ref().map(|t| (&s - t).gcd_euclid(n));
+        let found = g.as_ref().is_some_and(|g| !g.is_one() && g != n);
+        if i <= SQUARE_SEARCH_ROWS || t.is_some() || i == max_steps {
+            table_data.push(HartTable::new(
+                i.to_string(),
+                s.to_string(),
+                m.to_string(),
+                t.map_or("-".to_string(), |t| t.to_string()),
+                g.as_ref().map_or("-".to_string(), |g| g.to_string()),
+            ));
+        }
+        if found {
+            return (g, table_data);
+        }
+    }
+    (None, table_data)
+}
+
+///
+/// One multiplier of SQUFOF, the factor of n it gives if any. The rows of the forward
+/// and reverse cycles are appended to table_data.
+///
+fn squfof_multiplier(n: u64, k: u64, table_data: &mut Vec<SqufofTable>) -> Option<u64> {
+    let kn = n as u128 * k as u128;
+    let p0 = kn.isqrt() as i128;
+    let kn = kn as i128;
+    let row = |cycle: &str, i: u64, p: i128, q: i128, result: String| {
+        SqufofTable::new(
+            k.to_string(),
+            cycle.to_string(),
+            i.to_string(),
+            p.to_string(),
+            q.to_string(),
+            result,
+        )
+    };
+    let proper = |g: u64| g != 1 && g != n;
+    if p0 * p0 == kn {
+        let g = n.gcd(&(p0 as u64));
+        table_data.push(row("-", 0, p0, 0, format!("kn = P², gcd(n, P) = {}", g)));
+        return Some(g).filter(|&g| proper(g));
+    }
+    // about 2√2 (kn)¹ᐟ⁴ steps to a square Q, three times that before giving up
+    let bound = 6 * (2.0 * (kn as f64).sqrt()).sqrt() as u64;
+
+    // forward: Pᵢ = bQᵢ - Pᵢ₋₁, Qᵢ₊₁ = Qᵢ₋₁ + b(Pᵢ₋₁ - Pᵢ), b = ⌊(P₀ + Pᵢ₋₁)/Qᵢ⌋
+    let (mut p, mut q_prev, mut q) = (p0, 1i128, kn - p0 * p0);
+    table_data.push(row("forward", 1, p, q, "-".to_string()));
+    let mut i = 1;
+    while i < bound {
+        i += 1;
+        let b = (p0 + p) / q;
+        let p_next = b * q - p;
+        (p, q_prev, q) = (p_next, q, q_prev + b * (p - p_next));
+        let r = (q as u128).isqrt() as i128;
+        let square = i % 2 == 0 && r * r == q;
+        if !square {
+            if i <= SQUFOF_ROWS || i == bound {
+                table_data.push(row("forward", i, p, q, "-".to_string()));
+            }
+            continue;
+        }
+        table_data.push(row("forward", i, p, q, format!("Q = {}²", r)));
+
+        // reverse: from the square root of the quadratic form, until P repeats
+        let b = (p0 - p) / r;
+        let mut p_rev = b * r + p;
+        let (mut q_rev_prev, mut q_rev) = (r, (kn - p_rev * p_rev) / r);
+        let mut j = 0;
+        loop {
+            j += 1;
+            let b = (p0 + p_rev) / q_rev;
+            let p_prev = p_rev;
+            p_rev = b * q_rev - p_rev;
+            (q_rev_prev, q_rev) = (q_rev, q_rev_prev + b * (p_prev - p_rev));
+            if p_rev == p_prev || j == bound {
+                break;
+            }
+            if j <= SQUFOF_ROWS {
+                table_data.push(row("reverse", j, p_rev, q_rev, "-".to_string()));
+            }
+        }
+        let g = n.gcd(&(q_rev_prev as u64));
+        table_data.push(row(
+            "reverse",
+            j,
+            p_rev,
+            q_rev_prev,
+            format!("P repeats, gcd(n, Q) = {}", g),
+        ));
+        if proper(g) {
+            return Some(g);
+        }
+    }
+    None
+}
+
+///
+/// Shanks' square forms factorisation for n < 2⁶⁴. The continued fraction of √kn runs
+/// through the reduced forms (Qᵢ, Pᵢ) with Pᵢ² + Qᵢ₋₁Qᵢ = kn. A square Q₂ᵢ = r² at an
+/// even step gives the ambiguous form found by the reverse cycle, started from the
+/// square root of the form, which runs until P repeats; then Q shares a factor with n.
+/// Every number stays below √kn, O(n¹ᐟ⁴) steps of single word arithmetic. A multiplier
+/// that gives no factor within the bound is followed by the next one. Returns the factor
+/// and the first steps of each cycle, the squares and the ends of the reverse cycles.
+///
+pub fn squfof(n: &BigInt) -> (Option<BigInt>, Vec<SqufofTable>) {
+    let mut table_data: Vec<SqufofTable> = Vec::new();
+    if let Some(factor) = trivial_split(n) {
+        return (factor, table_data);
+    }
+    let Some(n) = n.to_u64() else {
+        return (None, table_data);
+    };
+    let r = n.isqrt();
+    if r * r == n {
+        return (Some(BigInt::from(r)), table_data);
+    }
+
+    for k in SQUFOF_MULTIPLIERS {
+        let g = n.gcd(&k);
+        if g != 1 && g != n {
+            return (Some(BigInt::from(g)), table_data);
+        }
+        if let Some(d) = squfof_multiplier(n, k, &mut table_data) {
+            return (Some(BigInt::from(d)), table_data);
+        }
+    }
+    (None, table_data)
+}
+
+///
+/// A nontrivial factor of the composite n, which has no prime factor below the trial
+/// division bound. Small n go to SQUFOF, then Pollard's rho, larger ones to ECM with
+/// B1 raised level by level. Up to SIQS_MAX_BITS the first levels are followed by SIQS,
+/// above it every level runs once and None is returned when all fail.
+///
+fn split(n: &BigInt, rng: &mut StdRng) -> Option<BigInt> {
+    if n.bits() <= SQUFOF_MAX_BITS {
+        if let Some(d) = squfof(n).0 {
+            return Some(d);
+        }
+    }
+    if n.bits() <= RHO_MAX_BITS {
+        return pollards_rho_brent(n, rng).0;
+    }
+    let levels = if n.bits() <= SIQS_MAX_BITS {
+        &ECM_LEVELS[..ECM_LEVELS_BEFORE_SIQS]
+    } else {
+        &ECM_LEVELS[..]
+    };
+    let factor = levels
+        .iter()
+        .find_map(|&(b1, curves)| ecm(n, b1, b1.saturating_mul(100), curves, rng).0);
+    if factor.is_some() || n.bits() > SIQS_MAX_BITS {
+        return factor;
+    }
+    siqs(n, None, None, rng, |_| {}).ok()?.factor
+}
+
+///
+/// The prime factorisation of n > 0, nothing for n <= 1. Trial division removes the
+/// primes below 2¹⁶, composite cofactors are split until every part passes the
+/// Baillie-PSW test. A part that SPLIT_ATTEMPTS attempts leave whole is returned
+/// separately as unfactored.
+///
+pub fn factorise(n: &BigInt, rng: &mut StdRng) -> (Vec<(BigInt, usize)>, Vec<BigInt>) {
+    let mut unfactored: Vec<BigInt> = Vec::new();
+    if n <= &BigInt::one() {
+        return (Vec::new(), unfactored);
+    }
+    let (mut factors, rest) = n.partial_prime_factors(TRIAL_DIVISION_BOUND);
+    let mut composites = vec![rest];
+
+    while let Some(m) = composites.pop() {
+        if m.is_one() {
+            continue;
+        }
+        if baillie_psw(&m) {
+            match factors.iter_mut().find(|(p, _)| p == &m) {
+                Some((_, e)) => *e += 1,
+                None => factors.push((m, 1)),
+            }
+            continue;
+        }
+        // rho and SQUFOF can fail for an unlucky choice of parameters, retry; a failed
+        // run of every ECM level and SIQS is not worth repeating
+        let attempts = if m.bits() <= RHO_MAX_BITS {
+            SPLIT_ATTEMPTS
+        } else {
+            1
+        };
+        match (0..attempts).find_map(|_| split(&m, rng)) {
+            Some(d) => {
+                composites.push(&m / &d);
+                composites.push(d);
+            }
+            None => unfactored.push(m),
+        }
+    }
+    factors.sort();
+    unfactored.sort();
+    (factors, unfactored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::seeded_rng;
+
+    #[test]
+    fn test_pollards_p_1() {
+        // 353 - 1 = 2⁵ x 11, both factors come out in the first batch, the replay
+        // separates them
+        let (factors, _) = pollards_p_1(&BigInt::from(78719u64), &BigInt::from(13u64), 100, 100);
+        assert_eq!(factors, Some((BigInt::from(223u64), BigInt::from(353u64))));
+
+        // 1000099 - 1 = 2 x 3² x 11 x 5051 needs stage 2, 10⁹ + 6 = 2 x 500000003 is not smooth
+        let n = BigInt::from(1000099u64) * BigInt::from(1000000007u64);
+        let (factors, table_data) = pollards_p_1(&n, &BigInt::from(2u64), 100, 10000);
+        assert_eq!(
+            factors,
+            Some((BigInt::from(1000000007u64), BigInt::from(1000099u64)))
+        );
+        assert!(table_data.iter().any(|row| row.stage() == "2"));
+        assert_eq!(pollards_p_1(&n, &BigInt::from(2u64), 100, 1000).0, None);
+
+        // a base ≡ 0 (mod n) is skipped rather than split off as n x 1
+        let (factors, _) = pollards_p_1(&BigInt::from(15u64), &BigInt::from(0u64), 100, 100);
+        assert_eq!(factors, Some((BigInt::from(5u64), BigInt::from(3u64))));
+        assert_eq!(
+            pollards_p_1(&BigInt::from(4u64), &BigInt::from(2u64), 10, 10).0,
+            Some((BigInt::from(2u64), BigInt::from(2u64)))
+        );
+    }
+
+    #[test]
+    fn test_lucas_v() {
+        // Vₖ(3) = φ²ᵏ + φ⁻²ᵏ, the Lucas numbers L₂ₖ
+        let n = BigInt::from(1000000007u64);
+        let l_2k = [2u64, 3, 7, 18, 47, 123, 322, 843, 2207];
+        for (k, l) in l_2k.iter().enumerate() {
+            assert_eq!(lucas_v(&BigInt::from(3u64), k as u64, &n), BigInt::from(*l));
+        }
+        let v_6 = lucas_v(&BigInt::from(3u64), 6, &n);
+        assert_eq!(lucas_v(&v_6, 7, &n), lucas_v(&BigInt::from(3u64), 42, &n));
+    }
+
+    #[test]
+    fn test_williams_p_plus_1() {
+        // 1001783 + 1 = 2³ x 3 x 7 x 67 x 89 while 1001783 - 1 = 2 x 500891
+        let q = BigInt::from(1000000007u64);
+        let n = BigInt::from(1001783u64) * &q;
+        let (factors, _) = williams_p_plus_1(&n, &BigInt::from(3u64), 100, 100);
+        assert_eq!(factors, Some((q.clone(), BigInt::from(1001783u64))));
+        assert_eq!(pollards_p_1(&n, &BigInt::from(2u64), 100, 10000).0, None);
+
+        // 1001447 + 1 = 2³ x 3² x 7 x 1987 needs stage 2
+        let n = BigInt::from(1001447u64) * &q;
+        let (factors, table_data) = williams_p_plus_1(&n, &BigInt::from(3u64), 100, 10000);
+        assert_eq!(factors, Some((q.clone(), BigInt::from(1001447u64))));
+        assert!(table_data.iter().any(|row| row.stage() == "2"));
+
+        // V - 2 < 0 must not give a negative gcd
+        let (factors, _) = williams_p_plus_1(&BigInt::from(15u64), &BigInt::from(1u64), 100, 100);
+        assert_eq!(factors, Some((BigInt::from(5u64), BigInt::from(3u64))));
+        let (factors, _) = williams_p_plus_1(&BigInt::from(6u64), &BigInt::from(3u64), 100, 100);
+        assert_eq!(factors, Some((BigInt::from(3u64), BigInt::from(2u64))));
+    }
+
+    #[test]
+    fn test_trivial_split() {
+        assert_eq!(trivial_split(&BigInt::zero()), Some(None));
+        assert_eq!(trivial_split(&BigInt::from(2u64)), Some(None));
+        assert_eq!(trivial_split(&BigInt::from(97u64)), Some(None));
+        assert_eq!(
+            trivial_split(&BigInt::from(4u64)),
+            Some(Some(BigInt::from(2u64)))
+        );
+        assert_eq!(trivial_split(&BigInt::from(91u64)), None);
+    }
+
+    #[test]
+    fn test_pollards_rho_brent() {
+        let (_, mut rng) = seeded_rng(Some(11));
+        // 10⁹ + 7 and 10⁹ + 9
+        let n = BigInt::from(1000000007u64) * BigInt::from(1000000009u64);
+        let (d, table_data) = pollards_rho_brent(&n, &mut rng);
+        let d = d.unwrap();
+        assert!(d == BigInt::from(1000000007u64) || d == BigInt::from(1000000009u64));
+        assert!(!table_data.is_empty());
+
+        assert_eq!(
+            pollards_rho_brent(&BigInt::from(1000003u64), &mut rng).0,
+            None
+        );
+    }
+
+    #[test]
+    fn test_fermat() {
+        // 1000003 and 1000033 are close, x = (p + q)/2 = 1000018 at the first step
+        let (p, q) = (BigInt::from(1000003u64), BigInt::from(1000033u64));
+        let (d, table_data) = fermat(&(&p * &q), 1, 1000);
+        assert_eq!(d, Some(p.clone()));
+        assert_eq!(table_data.len(), 1);
+
+        // 3000017 ≈ 3 x 1000003, k = 3 makes the factors of kn close
+        let n = &p * BigInt::from(3000017u64);
+        assert_eq!(fermat(&n, 1, 1000).0, None);
+        assert_eq!(fermat(&n, 3, 1000).0, Some(p));
+        assert_eq!(fermat(&BigInt::from(1000003u64), 1, 1000).0, None);
+    }
+
+    #[test]
+    fn test_lehman() {
+        let (p, q) = (BigInt::from(1000003u64), BigInt::from(1000000007u64));
+        let d = lehman(&(&p * &q)).0.unwrap();
+        assert!(d == p || d == q);
+        // 101 < n¹ᐟ³ turns up in the trial division
+        let (d, table_data) = lehman(&(BigInt::from(101u64) * &q));
+        assert_eq!(d, Some(BigInt::from(101u64)));
+        assert_eq!(table_data.len(), 1);
+        assert_eq!(lehman(&q).0, None);
+    }
+
+    #[test]
+    fn test_hart_one_line() {
+        let (p, q) = (BigInt::from(1000003u64), BigInt::from(1000033u64));
+        assert_eq!(hart_one_line(&(&p * &q), 10).0, Some(p.clone()));
+        let q = BigInt::from(1000000007u64);
+        let d = hart_one_line(&(&p * &q), 1000000).0.unwrap();
+        assert!(d == p || d == q);
+    }
+
+    #[test]
+    fn test_squfof() {
+        // 11111 = 41 x 271 with k = 1, the textbook example
+        let (d, table_data) = squfof(&BigInt::from(11111u64));
+        let d = d.unwrap();
+        assert!(d == BigInt::from(41u64) || d == BigInt::from(271u64));
+        assert!(table_data.iter().any(|row| row.cycle() == "reverse"));
+
+        let (p, q) = (BigInt::from(1000003u64), BigInt::from(1000000007u64));
+        let d = squfof(&(&p * &q)).0.unwrap();
+        assert!(d == p || d == q);
+        // (2³¹ - 1)(2³¹ + 11) and 4294967291 x 4294967279 near 2⁶⁴
+        let (p, q) = (BigInt::from(2147483647u64), BigInt::from(2147483659u64));
+        let d = squfof(&(&p * &q)).0.unwrap();
+        assert!(d == p || d == q);
+        let (p, q) = (BigInt::from(4294967291u64), BigInt::from(4294967279u64));
+        let d = squfof(&(&p * &q)).0.unwrap();
+        assert!(d == p || d == q);
+
+        assert_eq!(squfof(&BigInt::from(1000000007u64)).0, None);
+        assert_eq!(
+            squfof(&BigInt::from(3u64 * 1000003)).0,
+            Some(BigInt::from(3u64))
+        );
+    }
+
+    #[test]
+    fn test_factorise() {
+        let (_, mut rng) = seeded_rng(Some(5));
+        // 2³ x 3 x 999983² x 1000000007
+        let n = BigInt::from(24u64) * BigInt::from(999983u64).pow(2) * BigInt::from(1000000007u64);
+        assert_eq!(
+            factorise(&n, &mut rng).0,
+            vec![
+                (BigInt::from(2u64), 3),
+                (BigInt::from(3u64), 1),
+                (BigInt::from(999983u64), 2),
+                (BigInt::from(1000000007u64), 1)
+            ]
+        );
+        assert_eq!(factorise(&BigInt::one(), &mut rng), (vec![], vec![]));
+        assert_eq!(factorise(&BigInt::zero(), &mut rng), (vec![], vec![]));
+
+        // 10¹⁵ + 37 x (10²⁰ + 39) is past Pollard's rho, ECM splits it
+        let (p, q) = (
+            BigInt::from(1000000000000037u64),
+            BigInt::from(100000000000000000039u128),
+        );
+        assert_eq!(factorise(&(&p * &q), &mut rng).0, vec![(p, 1), (q, 1)]);
     }
 }
@@ -0,0 +1,704 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::Deserialize;
+
+/// Below this many rows left after filtering the nullspace comes from dense elimination.
+const GF2_LANCZOS_MIN_ROWS: usize = 500;
+/// Rows beyond the columns kept by the clique removal, each one more dependency.
+const GF2_EXCESS: usize = 96;
+/// Block Lanczos runs with a fresh random start before falling back to dense elimination.
+const GF2_LANCZOS_ATTEMPTS: u64 = 3;
+
+///
+/// A sparse matrix over GF(2), a row per relation holding the sorted columns where
+/// it is 1. The dependencies are subsets of rows summing to 0, the left nullspace.
+///
+#[derive(Clone, Debug, Default)]
+pub struct SparseMatrixGf2 {
+    columns: usize,
+    rows: Vec<Vec<u32>>,
+}
+
+///
+/// The dependencies among the rows of a matrix and how they were found: the rows
+/// removed as singletons and cliques, the size of what was left and the solver
+///
+#[derive(Clone, Debug)]
+pub struct Gf2Nullspace {
+    pub rows: usize,
+    pub columns: usize,
+    pub singletons: usize,
+    pub cliques: usize,
+    pub active_rows: usize,
+    pub active_columns: usize,
+    pub solver: &'static str,
+    pub iterations: usize,
+    pub dependencies: Vec<Vec<usize>>,
+}
+
+impl SparseMatrixGf2 {
+    pub fn new(columns: usize) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    ///
+    /// Appends a row given the columns of its odd entries, a column listed twice cancels
+    ///
+    pub fn push_row(&mut self, columns: impl IntoIterator<Item = u32>) {
+        let mut row = columns.into_iter().collect::<Vec<u32>>();
+        row.sort_unstable();
+        let mut odd: Vec<u32> = Vec::with_capacity(row.len());
+        for c in row {
+            if odd.last() == Some(&c) {
+                odd.pop();
+            } else {
+                odd.push(c);
+            }
+        }
+        if let Some(&c) = odd.last() {
+            self.columns = self.columns.max(c as usize + 1);
+        }
+        self.rows.push(odd);
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    ///
+    /// Removes the rows that cannot be in a dependency and the excess rows. A column
+    /// with a single 1 (a singleton) rules its row out, and removing it can make further
+    /// singletons. A clique is a set of rows joined by columns with exactly two 1s, deleting
+    /// one costs a row more than the columns it frees, so the largest go first while the
+    /// excess is above GF2_EXCESS. Returns the rows kept and the rows removed as singletons
+    /// and as cliques.
+    ///
+    fn filter(&self) -> (Vec<usize>, usize, usize) {
+        let mut weight = vec![0usize; self.columns];
+        for row in &self.rows {
+            row.iter().for_each(|&c| weight[c as usize] += 1);
+        }
+        let mut alive = vec![true; self.rows.len()];
+        let (mut singletons, mut cliques) = (0, 0);
+
+        let remove = |r: usize, alive: &mut Vec<bool>, weight: &mut Vec<usize>| {
+            alive[r] = false;
+            self.rows[r].iter().for_each(|&c| weight[c as usize] -= 1);
+        };
+        loop {
+            loop {
+                let lone = (0..self.rows.len())
+                    .filter(|&r| alive[r])
+                    .filter(|&r| self.rows[r].iter().any(|&c| weight[c as usize] == 1))
+                    .collect::<Vec<usize>>();
+                if lone.is_empty() {
+                    break;
+                }
+                singletons += lone.len();
+                for r in lone {
+                    remove(r, &mut alive, &mut weight);
+                }
+            }
+
+            let rows = alive.iter().filter(|&&a| a).count();
+            let columns = weight.iter().filter(|&&w| w > 0).count();
+            if rows <= columns + GF2_EXCESS {
+                break;
+            }
+
+            // components of the graph on the rows with an edge per column of weight 2
+            let mut parent = (0..self.rows.len()).collect::<Vec<usize>>();
+            fn root(parent: &mut [usize], mut r: usize) -> usize {
+                while parent[r] != r {
+                    parent[r] = parent[parent[r]];
+                    r = parent[r];
+                }
+                r
+            }
+            let mut first: HashMap<u32, usize> = HashMap::new();
+            for r in (0..self.rows.len()).filter(|&r| alive[r]) {
+                for &c in self.rows[r].iter().filter(|&&c| weight[c as usize] == 2) {
+                    if let Some(&s) = first.get(&c) {
+                        let (a, b) = (root(&mut parent, r), root(&mut parent, s));
+                        parent[a] = b;
+                    } else {
+                        first.insert(c, r);
+                    }
+                }
+            }
+            let mut components: HashMap<usize, Vec<usize>> = HashMap::new();
+            for r in (0..self.rows.len()).filter(|&r| alive[r]) {
+                components.entry(root(&mut parent, r)).or_default().push(r);
+            }
+            let mut components = components.into_values().collect::<Vec<Vec<usize>>>();
+            components.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+            for clique in components.iter().take(rows - columns - GF2_EXCESS) {
+                for &r in clique {
+                    remove(r, &mut alive, &mut weight);
+                    cliques += 1;
+                }
+            }
+        }
+        let kept = (0..self.rows.len()).filter(|&r| alive[r]).collect();
+        (kept, singletons, cliques)
+    }
+
+    ///
+    /// The dependencies among the rows, each a list of row indices. The matrix is filtered
+    /// first, what is left goes to Block Lanczos, or to Gaussian elimination when it is small
+    /// or Block Lanczos keeps failing.
+    ///
+    pub fn nullspace(&self) -> Gf2Nullspace {
+        let (kept, singletons, cliques) = self.filter();
+        let mut index: HashMap<u32, u32> = HashMap::new();
+        let mut active = SparseMatrixGf2::new(0);
+        for &r in &kept {
+            let row = self.rows[r]
+                .iter()
+                .map(|c| {
+                    let next = index.len() as u32;
+                    *index.entry(*c).or_insert(next)
+                })
+                .collect::<Vec<u32>>();
+            active.push_row(row);
+        }
+        active.columns = index.len();
+
+        let mut nullspace = Gf2Nullspace {
+            rows: self.rows.len(),
+            columns: self.columns,
+            singletons,
+            cliques,
+            active_rows: active.rows(),
+            active_columns: active.columns(),
+            solver: "Gaussian elimination",
+            iterations: 0,
+            dependencies: Vec::new(),
+        };
+        let mut dependencies = None;
+        if active.rows() >= GF2_LANCZOS_MIN_ROWS {
+            for attempt in 0..GF2_LANCZOS_ATTEMPTS {
+                let mut rng = StdRng::seed_from_u64(attempt);
+                if let Some((found, iterations)) = block_lanczos(&active, &mut rng) {
+                    nullspace.solver = "Block Lanczos";
+                    nullspace.iterations = iterations;
+                    dependencies = Some(found);
+                    break;
+                }
+            }
+        }
+        let dependencies = dependencies.unwrap_or_else(|| {
+            let rows = active
+                .rows
+                .iter()
+                .map(|row| {
+                    let mut bits = vec![0u64; active.columns.div_ceil(64)];
+                    row.iter()
+                        .for_each(|&c| bits[c as usize / 64] |= 1 << (c % 64));
+                    bits
+                })
+                .collect::<Vec<Vec<u64>>>();
+            gaussian_elimination_gf2(&rows, active.columns)
+        });
+        nullspace.dependencies = dependencies
+            .into_iter()
+            .map(|dependency| dependency.into_iter().map(|r| kept[r]).collect())
+            .collect();
+        nullspace
+    }
+
+    ///
+    /// Bv for an N x 64 block v over the rows, one word per row
+    ///
+    fn mul(&self, v: &[u64]) -> Vec<u64> {
+        let mut w = vec![0u64; self.columns];
+        for (row, x) in self.rows.iter().zip(v) {
+            row.iter().for_each(|&c| w[c as usize] ^= x);
+        }
+        w
+    }
+
+    ///
+    /// Bᵀw, a word per row
+    ///
+    fn mul_transpose(&self, w: &[u64]) -> Vec<u64> {
+        self.rows
+            .par_iter()
+            .map(|row| row.iter().fold(0, |acc, &c| acc ^ w[c as usize]))
+            .collect()
+    }
+}
+
+///
+/// The dependencies among the rows of a matrix over GF(2), each row a bitset of
+/// columns. Gaussian elimination, every row carrying the set of rows it was built
+/// from; a row that is reduced to zero gives a subset of rows summing to 0.
+///
+pub fn gaussian_elimination_gf2(rows: &[Vec<u64>], columns: usize) -> Vec<Vec<usize>> {
+    let words = rows.len().div_ceil(64);
+    let mut matrix = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut history = vec![0u64; words];
+            history[i / 64] |= 1 << (i % 64);
+            (row.clone(), history)
+        })
+        .collect::<Vec<(Vec<u64>, Vec<u64>)>>();
+
+    let mut pivot_rows = vec![false; rows.len()];
+    for column in 0..columns {
+        let (word, bit) = (column / 64, 1u64 << (column % 64));
+        let Some(pivot) =
+            (0..matrix.len()).find(|&r| !pivot_rows[r] && matrix[r].0[word] & bit != 0)
+        else {
+            continue;
+        };
+        pivot_rows[pivot] = true;
+        let (pivot_row, pivot_history) = matrix[pivot].clone();
+        for (r, (row, history)) in matrix.iter_mut().enumerate() {
+            if r != pivot && row[word] & bit != 0 {
+                row.iter_mut().zip(&pivot_row).for_each(|(x, y)| *x ^= y);
+                history
+                    .iter_mut()
+                    .zip(&pivot_history)
+                    .for_each(|(x, y)| *x ^= y);
+            }
+        }
+    }
+
+    matrix
+        .iter()
+        .filter(|(row, _)| row.iter().all(|x| *x == 0))
+        .map(|(_, history)| {
+            (0..rows.len())
+                .filter(|i| history[i / 64] >> (i % 64) & 1 == 1)
+                .collect()
+        })
+        .collect()
+}
+
+/// A 64 x 64 matrix over GF(2), row i as a word with bit j the entry (i, j).
+type Block = [u64; 64];
+
+const IDENTITY: Block = {
+    let mut identity = [0u64; 64];
+    let mut i = 0;
+    while i < 64 {
+        identity[i] = 1 << i;
+        i += 1;
+    }
+    identity
+};
+
+///
+/// xᵀy for two N x 64 blocks
+///
+fn inner(x: &[u64], y: &[u64]) -> Block {
+    x.par_chunks(4096)
+        .zip(y.par_chunks(4096))
+        .map(|(x, y)| {
+            let mut product = [0u64; 64];
+            for (&a, &b) in x.iter().zip(y) {
+                let mut a = a;
+                while a != 0 {
+                    product[a.trailing_zeros() as usize] ^= b;
+                    a &= a - 1;
+                }
+            }
+            product
+        })
+        .reduce(
+            || [0u64; 64],
+            |mut p, q| {
+                p.iter_mut().zip(q).for_each(|(x, y)| *x ^= y);
+                p
+            },
+        )
+}
+
+///
+/// ab for two 64 x 64 blocks
+///
+fn mul_block(a: &Block, b: &Block) -> Block {
+    let mut product = [0u64; 64];
+    for (p, &row) in product.iter_mut().zip(a) {
+        let mut row = row;
+        while row != 0 {
+            *p ^= b[row.trailing_zeros() as usize];
+            row &= row - 1;
+        }
+    }
+    product
+}
+
+///
+/// y += vm for an N x 64 block v, eight tables of the sums of the rows of m by byte
+///
+fn mul_add(v: &[u64], m: &Block, y: &mut [u64]) {
+    let mut tables = vec![[0u64; 256]; 8];
+    for (k, table) in tables.iter_mut().enumerate() {
+        for b in 1..256usize {
+            let low = b.trailing_zeros() as usize;
+            table[b] = table[b & (b - 1)] ^ m[8 * k + low];
+        }
+    }
+    y.par_iter_mut().zip(v).for_each(|(y, &x)| {
+        *y ^= tables.iter().enumerate().fold(0, |acc, (k, table)| {
+            acc ^ table[(x >> (8 * k)) as usize & 0xff]
+        });
+    });
+}
+
+///
+/// Montgomery's choice of the columns Sᵢ of VᵢᵀAVᵢ: a nonsingular submatrix taking in
+/// every column left out of Sᵢ₋₁, and the inverse of VᵢᵀAVᵢ restricted to it, 0 outside.
+/// None when no such submatrix exists.
+///
+fn nonsingular_submatrix(t: &Block, last: u64) -> Option<(Block, u64)> {
+    let mut m = (0..64)
+        .map(|i| [t[i], 1u64 << i])
+        .collect::<Vec<[u64; 2]>>();
+    // the columns not in Sᵢ₋₁ come first
+    let order = (0..64)
+        .filter(|i| last >> i & 1 == 0)
+        .chain((0..64).filter(|i| last >> i & 1 == 1))
+        .collect::<Vec<usize>>();
+
+    let mut chosen = 0u64;
+    for (i, &s) in order.iter().enumerate() {
+        let mask = 1u64 << s;
+        if let Some(&j) = order[i..].iter().find(|&&j| m[j][0] & mask != 0) {
+            m.swap(s, j);
+            let pivot = m[s];
+            for (r, row) in m.iter_mut().enumerate() {
+                if r != s && row[0] & mask != 0 {
+                    row[0] ^= pivot[0];
+                    row[1] ^= pivot[1];
+                }
+            }
+            chosen |= mask;
+            continue;
+        }
+        // no pivot, the right half makes up for the missing column and the row is dropped
+        let &j = order[i..].iter().find(|&&j| m[j][1] & mask != 0)?;
+        m.swap(s, j);
+        let pivot = m[s];
+        for (r, row) in m.iter_mut().enumerate() {
+            if r != s && row[1] & mask != 0 {
+                row[0] ^= pivot[0];
+                row[1] ^= pivot[1];
+            }
+        }
+        m[s] = [0, 0];
+    }
+    let mut inverse = [0u64; 64];
+    inverse.iter_mut().zip(&m).for_each(|(w, row)| *w = row[1]);
+    Some((inverse, chosen))
+}
+
+///
+/// Montgomery's Block Lanczos on A = BᵀB, B with a column per row of the matrix, 64
+/// vectors at a time. From a random Y it solves Ax = AY over the Krylov subspace of AY,
+/// the Vᵢ A-orthogonal blocks of it:
+///
+/// Vᵢ₊₁ = AVᵢSᵢSᵢᵀ + VᵢDᵢ₊₁ + Vᵢ₋₁Eᵢ₊₁ + Vᵢ₋₂Fᵢ₊₁, x = Σ VᵢWᵢVᵢᵀV₀
+///
+/// until VᵢᵀAVᵢ = 0, after about N/63 steps. x - Y and the last Vᵢ are then nearly in the
+/// nullspace of B, a final elimination on their images under B gives the combinations that
+/// are. Returns the dependencies and the iterations, or None when the iteration breaks down.
+///
+fn block_lanczos(matrix: &SparseMatrixGf2, rng: &mut StdRng) -> Option<(Vec<Vec<usize>>, usize)> {
+    let n = matrix.rows();
+    let apply = |v: &[u64]| matrix.mul_transpose(&matrix.mul(v));
+
+    let y = (0..n).map(|_| rng.gen::<u64>()).collect::<Vec<u64>>();
+    let v0 = apply(&y);
+    let mut v = [v0.clone(), vec![0u64; n], vec![0u64; n]];
+    let mut x = vec![0u64; n];
+    let mut winv = [[0u64; 64]; 3];
+    let (mut vt_a_v1, mut vt_a2_v1) = ([0u64; 64], [0u64; 64]);
+    let mut mask1 = u64::MAX;
+    let mut iterations = 0;
+
+    loop {
+        let mut av = apply(&v[0]);
+        let vt_a_v = inner(&v[0], &av);
+        let vt_a2_v = inner(&av, &av);
+        if vt_a_v.iter().all(|&w| w == 0) {
+            break;
+        }
+        iterations += 1;
+        let (w, mask0) = nonsingular_submatrix(&vt_a_v, mask1)?;
+        if mask0 == 0 || mask0 | mask1 != u64::MAX {
+            return None;
+        }
+        winv[0] = w;
+
+        // AVᵢSᵢSᵢᵀ
+        if mask0 != u64::MAX {
+            av.par_iter_mut().for_each(|x| *x &= mask0);
+        }
+        // D = I - Wᵢ(VᵢᵀA²VᵢSᵢSᵢᵀ + VᵢᵀAVᵢ)
+        let mut d = [0u64; 64];
+        for i in 0..64 {
+            d[i] = (vt_a2_v[i] & mask0) ^ vt_a_v[i];
+        }
+        let mut d = mul_block(&winv[0], &d);
+        d.iter_mut().zip(IDENTITY).for_each(|(x, y)| *x ^= y);
+        // E = -Wᵢ₋₁VᵢᵀAVᵢSᵢSᵢᵀ
+        let mut e = vt_a_v;
+        e.iter_mut().for_each(|x| *x &= mask0);
+        let e = mul_block(&winv[1], &e);
+        // F = -Wᵢ₋₂(I - Vᵢ₋₁ᵀAVᵢ₋₁Wᵢ₋₁)(Vᵢ₋₁ᵀA²Vᵢ₋₁Sᵢ₋₁Sᵢ₋₁ᵀ + Vᵢ₋₁ᵀAVᵢ₋₁)SᵢSᵢᵀ
+        let mut f = mul_block(&vt_a_v1, &winv[1]);
+        f.iter_mut().zip(IDENTITY).for_each(|(x, y)| *x ^= y);
+        let f = mul_block(&winv[2], &f);
+        let mut f2 = [0u64; 64];
+        for i in 0..64 {
+            f2[i] = ((vt_a2_v1[i] & mask1) ^ vt_a_v1[i]) & mask0;
+        }
+        let f = mul_block(&f, &f2);
+
+        mul_add(&v[0], &d, &mut av);
+        mul_add(&v[1], &e, &mut av);
+        mul_add(&v[2], &f, &mut av);
+        let vt_v0 = inner(&v[0], &v0);
+        mul_add(&v[0], &mul_block(&winv[0], &vt_v0), &mut x);
+
+        v.rotate_right(1);
+        v[0] = av;
+        winv.copy_within(0..2, 1);
+        (vt_a_v1, vt_a2_v1, mask1) = (vt_a_v, vt_a2_v, mask0);
+    }
+
+    // the columns of [x - Y | Vₘ] and their images under B, reduced by column operations
+    let mut vectors = x
+        .iter()
+        .zip(&y)
+        .zip(&v[0])
+        .map(|((x, y), v)| (x ^ y) as u128 | (*v as u128) << 64)
+        .collect::<Vec<u128>>();
+    let low = vectors.iter().map(|&w| w as u64).collect::<Vec<u64>>();
+    let high = vectors
+        .iter()
+        .map(|&w| (w >> 64) as u64)
+        .collect::<Vec<u64>>();
+    let mut images = matrix
+        .mul(&low)
+        .into_iter()
+        .zip(matrix.mul(&high))
+        .map(|(l, h)| l as u128 | (h as u128) << 64)
+        .collect::<Vec<u128>>();
+    let mut kernel = u128::MAX;
+    for r in 0..images.len() {
+        let row = images[r] & kernel;
+        if row == 0 {
+            continue;
+        }
+        let pivot = row.trailing_zeros();
+        let others = row & !(1u128 << pivot);
+        for w in images.iter_mut().chain(vectors.iter_mut()) {
+            if *w >> pivot & 1 == 1 {
+                *w ^= others;
+            }
+        }
+        kernel &= !(1u128 << pivot);
+    }
+
+    let mut dependencies = (0..128)
+        .filter(|b| kernel >> b & 1 == 1)
+        .map(|b| {
+            (0..n)
+                .filter(|&r| vectors[r] >> b & 1 == 1)
+                .collect::<Vec<usize>>()
+        })
+        .filter(|dependency| !dependency.is_empty())
+        .collect::<Vec<Vec<usize>>>();
+    dependencies.sort();
+    dependencies.dedup();
+    if dependencies.is_empty() {
+        return None;
+    }
+    Some((dependencies, iterations))
+}
+
+///
+/// A relation of a SIQS checkpoint, the factors as (index, exponent)
+///
+#[derive(Deserialize)]
+struct CheckpointRelation {
+    factors: Vec<(u32, u32)>,
+    large: u64,
+}
+
+///
+/// Relations read from a file and the lines behind each row of the matrix
+///
+#[derive(Clone, Debug)]
+pub struct RelationFile {
+    pub matrix: SparseMatrixGf2,
+    pub lines: Vec<Vec<usize>>,
+}
+
+///
+/// Reads a matrix a relation per line. A line is either the columns of the relation, separated
+/// by spaces or commas and each with an optional exponent as in 3^2, or a relation of a SIQS
+/// checkpoint, whose header is skipped and whose partial relations are paired up by their
+/// large prime. Blank lines and lines starting with # are ignored.
+///
+pub fn read_relations(path: &str) -> Result<RelationFile, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("error: {}: {}\n", path, e))?;
+    let mut matrix = SparseMatrixGf2::new(0);
+    let mut lines: Vec<Vec<usize>> = Vec::new();
+    let mut partials: HashMap<u64, (usize, Vec<u32>)> = HashMap::new();
+
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("error: {}: {}\n", path, e))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('{') {
+            let Ok(relation) = serde_json::from_str::<CheckpointRelation>(line) else {
+                // the checkpoint header
+                continue;
+            };
+            let odd = relation
+                .factors
+                .iter()
+                .filter(|(_, e)| e % 2 == 1)
+                .map(|&(c, _)| c)
+                .collect::<Vec<u32>>();
+            if relation.large == 1 {
+                matrix.push_row(odd);
+                lines.push(vec![i + 1]);
+            } else if let Some((first, columns)) = partials.get(&relation.large) {
+                matrix.push_row(columns.iter().chain(&odd).copied());
+                lines.push(vec![*first, i + 1]);
+            } else {
+                partials.insert(relation.large, (i + 1, odd));
+            }
+            continue;
+        }
+
+        let mut columns = Vec::new();
+        for entry in line.split(|c: char| c.is_whitespace() || c == ',') {
+            if entry.is_empty() {
+                continue;
+            }
+            let (column, exponent) = entry.split_once('^').unwrap_or((entry, "1"));
+            let parse = |s: &str| {
+                s.parse::<u32>()
+                    .map_err(|e| format!("error: {}:{}: {}: {}\n", path, i + 1, entry, e))
+            };
+            if parse(exponent)? % 2 == 1 {
+                columns.push(parse(column)?);
+            }
+        }
+        matrix.push_row(columns);
+        lines.push(vec![i + 1]);
+    }
+    Ok(RelationFile { matrix, lines })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_dependency(matrix: &SparseMatrixGf2, dependency: &[usize]) -> bool {
+        let mut sum = vec![false; matrix.columns()];
+        for &r in dependency {
+            matrix.rows[r].iter().for_each(|&c| sum[c as usize] ^= true);
+        }
+        !dependency.is_empty() && sum.iter().all(|s| !s)
+    }
+
+    #[test]
+    fn test_gaussian_elimination_gf2() {
+        // rows 0 + 1 + 2 = 0 and row 3 = 0
+        let rows = vec![vec![0b011], vec![0b110], vec![0b101], vec![0b000]];
+        let mut dependencies = gaussian_elimination_gf2(&rows, 3);
+        dependencies.sort();
+        assert_eq!(dependencies, vec![vec![0, 1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut matrix = SparseMatrixGf2::new(5);
+        // column 4 is a singleton, removing row 4 leaves column 3 a singleton in row 3
+        for row in [vec![0, 1], vec![1, 2], vec![0, 2], vec![2, 3], vec![3, 4]] {
+            matrix.push_row(row);
+        }
+        assert_eq!(matrix.filter(), (vec![0, 1, 2], 2, 0));
+        let nullspace = matrix.nullspace();
+        assert_eq!(nullspace.dependencies, vec![vec![0, 1, 2]]);
+
+        let mut matrix = SparseMatrixGf2::new(0);
+        matrix.push_row([7, 2, 7, 2, 2]);
+        assert_eq!(matrix.rows, vec![vec![2]]);
+        assert_eq!(matrix.columns(), 3);
+    }
+
+    #[test]
+    fn test_block_lanczos() {
+        // 3000 rows of about 20 columns over 2800 columns, weighted to small columns like
+        // the factor base of a sieve
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut matrix = SparseMatrixGf2::new(2800);
+        for _ in 0..3000 {
+            let row = (0..20)
+                .map(|_| {
+                    let u: f64 = rng.gen();
+                    (2800f64.powf(u * u) as u32).min(2799)
+                })
+                .collect::<Vec<u32>>();
+            matrix.push_row(row);
+        }
+        let nullspace = matrix.nullspace();
+        assert_eq!(nullspace.solver, "Block Lanczos");
+        assert!(nullspace.singletons > 0);
+        assert!(nullspace.dependencies.len() >= 32);
+        assert!(nullspace
+            .dependencies
+            .iter()
+            .all(|dependency| is_dependency(&matrix, dependency)));
+    }
+
+    #[test]
+    fn test_read_relations() {
+        let path = std::env::temp_dir().join("nt-tools-test-relations.txt");
+        let path = path.to_str().unwrap();
+        let text = "# columns\n0 1\n1,2\n\n0 2 3^2\n\
+                    {\"n\":\"91\",\"multiplier\":1,\"factor_base\":3}\n\
+                    {\"v\":\"5\",\"factors\":[[1,1],[2,3]],\"large\":1}\n\
+                    {\"v\":\"6\",\"factors\":[[0,1]],\"large\":101}\n\
+                    {\"v\":\"7\",\"factors\":[[2,1]],\"large\":101}\n";
+        std::fs::write(path, text).unwrap();
+        let relations = read_relations(path).unwrap();
+        assert_eq!(
+            relations.matrix.rows,
+            vec![vec![0, 1], vec![1, 2], vec![0, 2], vec![1, 2], vec![0, 2]]
+        );
+        assert_eq!(
+            relations.lines,
+            vec![vec![2], vec![3], vec![5], vec![7], vec![8, 9]]
+        );
+
+        std::fs::write(path, "0 1\n2 x\n").unwrap();
+        assert!(read_relations(path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,54 +1,94 @@
 #![warn(unused_variables)]
 #![allow(dead_code)]
+mod additive;
+mod arithmetic_functions;
+mod certificates;
 mod cli;
 mod cli_ops;
 mod display;
+mod elliptic_curves;
 mod factorisations;
+mod gf2;
 mod groups_modulo_n;
 mod logarithms;
 mod presets;
 mod primality;
 mod prime_factors;
+mod prime_generation;
 mod quadratic_sieve;
+mod sieve;
+mod siqs;
 mod utils;
 
 use std::{clone, collections::HashMap, io::Write};
 
-use factorisations::pollards_p_1;
+use elliptic_curves::ecm;
+use factorisations::{
+    factorise, fermat, hart_one_line, lehman, p_1_bounds, pollards_p_1, pollards_rho_brent, squfof,
+    trivial_split, williams_p_plus_1,
+};
+use gf2::read_relations;
 use json_to_table::json_to_table;
 use num_iter::range_inclusive;
-use quadratic_sieve::prepare_matrix;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use quadratic_sieve::quadratic_sieve;
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use siqs::siqs;
 use tabled::{
-    settings::{panel::Header, split::Split, Style},
+    settings::{
+        location::ByColumnName, object::Columns, panel::Header, split::Split, Disable, Style,
+    },
     Table,
 };
 
-use cli::{cli, CarmichaelMethods, PrimalityMethods};
-use fmtastic::Superscript;
+use additive::{
+    euler_factorisation, goldbach_count, goldbach_minimal, sums_of_squares_table, ternary_goldbach,
+    SumsOfSquares,
+};
+use arithmetic_functions::arithmetic_table;
+use certificates::{certify, verify_certificate, Certificate};
+use cli::{cli, ArithmeticFunction, CarmichaelMethods, CertificateMethods, PrimalityMethods};
+use fmtastic::{Subscript, Superscript};
+use prime_generation::{random_prime, random_provable_prime, PrimeKind};
 use serde_json::json;
+use sieve::{
+    composites_in_range, logarithmic_integral, next_prime, nth_prime, prime_count, primes_in_range,
+    PRIME_COUNT_LIMIT,
+};
 
-use display::{matrix_print, Matrix};
+use display::{format_prime_factors_print, matrix_print, Matrix};
 use num_bigint::BigInt;
+use num_integer::Integer;
 use num_traits::{One, ToPrimitive, Zero};
-use presets::{
-    find_primes_in_range_trial_division_parallel, list_carmichael_nums, list_prime_factors_in_range,
-};
+use presets::{list_carmichael_nums, list_prime_factors_in_range};
 use primality::{aks, carmichael_nums_flt, carmichael_nums_korselt, gcd_test};
-use utils::findr;
 
 use crate::{
-    display::{NumFactorTable, P_k_2P_kTable, PrimitiveRootsTable},
+    display::{
+        fermat_output_print, miller_rabin_output_print, solovay_strassen_output_print,
+        AksFailedTable, AksTable, ArithmeticTable, GapHistogramTable, GeneratedPrimeTable,
+        Gf2DependencyTable, GoldbachTable, MillerRabinTable, NthPrimeTable, NumFactorTable,
+        P_k_2P_kTable, PepinTable, PrimeCountTable, PrimeGapTable, PrimitiveRootsTable,
+        SquaresTable,
+    },
     groups_modulo_n::{
         euler_totient_phi, is_integer_of_form_pk_2pk, primitive_roots_trial_n_error,
     },
-    presets::{find_miller_rabin_liars, search_nums_with_primitive_roots, NumCategory},
-    primality::{is_prime_trial_division_parallel, miller_rabin_primality, AksSteps},
+    presets::{
+        find_euler_jacobi_liars, find_miller_rabin_liars, list_mersenne_numbers, prime_gaps,
+        prime_tuples, search_nums_with_primitive_roots, tuple_pattern, NumCategory,
+    },
+    primality::{
+        aks_find_r, baillie_psw, fermat_test, is_prime_trial_division_parallel, miller_rabin,
+        miller_rabin_deterministic_bases, miller_rabin_test, pepin, proth, solovay_strassen,
+        AksSteps, AksTrace, MillerRabinVerdict, PEPIN_MAX_M,
+    },
     prime_factors::PrimeFactors,
-    utils::{modular_pow, Gcd},
+    utils::{modular_pow, random_bases, seeded_rng, Gcd},
 };
 
 fn main() -> Result<(), String> {
+    // `nt-tools --seed N` fixes the seed of the randomised algorithms for the session
+    let mut session_seed = seed_from_args(std::env::args().skip(1))?;
     loop {
         let line = readline()?;
         let line = line.trim();
@@ -58,7 +98,7 @@ fn main() -> Result<(), String> {
             continue;
         }
 
-        match respond(line) {
+        match respond(line, &mut session_seed) {
             Ok(quit) => {
                 if quit {
                     break;
@@ -74,7 +114,7 @@ fn main() -> Result<(), String> {
     Ok(())
 }
 
-fn respond(line: &str) -> Result<bool, String> {
+fn respond(line: &str, session_seed: &mut Option<u64>) -> Result<bool, String> {
     let args = shlex::split(line).ok_or("error: Invalid quoting")?;
     let matches = cli()
         .try_get_matches_from(args)
@@ -83,7 +123,7 @@ fn respond(line: &str) -> Result<bool, String> {
         Some(("primes", matches)) => {
             let s = matches.get_one::<BigInt>("start").expect("required");
             let e = matches.get_one::<BigInt>("end").expect("required");
-            let (primes, _) = find_primes_in_range_trial_division_parallel(s.clone(), e.clone());
+            let primes = primes_in_range(s, e);
 
             let table_data = &primes
                 .iter()
@@ -95,8 +135,7 @@ fn respond(line: &str) -> Result<bool, String> {
         Some(("composites", matches)) => {
             let s = matches.get_one::<BigInt>("start").expect("required");
             let e = matches.get_one::<BigInt>("end").expect("required");
-            let (_, composites) =
-                find_primes_in_range_trial_division_parallel(s.clone(), e.clone());
+            let composites = composites_in_range(s, e);
 
             let table_data = &composites
                 .iter()
@@ -104,11 +143,269 @@ fn respond(line: &str) -> Result<bool, String> {
                 .collect::<Vec<Matrix>>();
             matrix_print(
                 table_data,
-                "Prime Numbers:".to_string(),
+                "Composite Numbers:".to_string(),
                 &composites.len() / 14,
             );
             //std::io::stdout().flush().map_err(|e| e.to_string())?;
         }
+        Some(("next-prime", matches)) => {
+            let n = matches.get_one::<BigInt>("num").expect("required");
+            println!("\nSmallest prime >= {} is {}\n", n, next_prime(n));
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("prime-count", matches)) => {
+            let xs = matches
+                .get_many::<u64>("x")
+                .expect("required")
+                .copied()
+                .collect::<Vec<u64>>();
+            if let Some(x) = xs.iter().find(|x| !(2..=PRIME_COUNT_LIMIT).contains(*x)) {
+                return Err(format!(
+                    "error: x = {} is not between 2 and {}\n",
+                    x, PRIME_COUNT_LIMIT
+                ));
+            }
+
+            let table_data = xs
+                .iter()
+                .map(|x| {
+                    let pi = prime_count(*x);
+                    let x_f = *x as f64;
+                    let (x_over_ln_x, li) = (x_f / x_f.ln(), logarithmic_integral(x_f));
+                    let error = |estimate: f64| {
+                        if pi == 0 {
+                            "-".to_string()
+                        } else {
+                            format!("{:+.6}%", 100.0 * (estimate - pi as f64) / pi as f64)
+                        }
+                    };
+                    PrimeCountTable::new(
+                        x.to_string(),
+                        pi.to_string(),
+                        format!("{:.1}", x_over_ln_x),
+                        format!("{:.1}", li),
+                        error(x_over_ln_x),
+                        error(li),
+                    )
+                })
+                .collect::<Vec<PrimeCountTable>>();
+
+            let mut table = Table::new(&table_data);
+            table.with(Style::modern());
+            if !matches.get_flag("estimates") {
+                table.with(Disable::column(Columns::new(2..)));
+            }
+            println!("\nPrime Counting Function π(x):");
+            println!("{table}\n");
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("nth-prime", matches)) => {
+            let ns = matches
+                .get_many::<u64>("n")
+                .expect("required")
+                .copied()
+                .collect::<Vec<u64>>();
+
+            let table_data = ns
+                .iter()
+                .map(|n| {
+                    Ok(NthPrimeTable::new(
+                        n.to_string(),
+                        nth_prime(*n)?.to_string(),
+                    ))
+                })
+                .collect::<Result<Vec<NthPrimeTable>, String>>()?;
+
+            let mut table = Table::new(&table_data);
+            table.with(Style::modern());
+            println!("\nThe nth Prime pₙ:");
+            println!("{table}\n");
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("prime-gaps", matches)) => {
+            let s = matches.get_one::<u64>("start").expect("required");
+            let e = matches.get_one::<u64>("end").expect("required");
+            let gaps = prime_gaps(*s, *e)?;
+            if gaps.records.is_empty() {
+                return Err(format!(
+                    "error: fewer than two primes between {} and {}\n",
+                    s, e
+                ));
+            }
+
+            let table_data = gaps
+                .records
+                .iter()
+                .map(|(gap, p)| {
+                    PrimeGapTable::new(gap.to_string(), p.to_string(), (p + gap).to_string())
+                })
+                .collect::<Vec<PrimeGapTable>>();
+            let mut table = Table::new(&table_data);
+            table.with(Style::modern());
+            println!(
+                "\nRecord Gaps between the {} primes in [{}, {}]:",
+                gaps.prime_count, s, e
+            );
+            println!("{table}");
+            let (gap, p) = gaps.records.last().unwrap();
+            println!("Maximal gap {} after p = {}\n", gap, p);
+
+            if matches.get_flag("histogram") {
+                let total = gaps.prime_count - 1;
+                let table_data = gaps
+                    .histogram
+                    .iter()
+                    .map(|(gap, count)| {
+                        GapHistogramTable::new(
+                            gap.to_string(),
+                            count.to_string(),
+                            format!("{:.4}%", 100.0 * *count as f64 / total as f64),
+                        )
+                    })
+                    .collect::<Vec<GapHistogramTable>>();
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("Gap Histogram:");
+                println!("{table}\n");
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("prime-tuples", matches)) => {
+            let s = matches.get_one::<u64>("start").expect("required");
+            let e = matches.get_one::<u64>("end").expect("required");
+            let pattern = matches.get_one::<String>("pattern").expect("default");
+            let offsets = tuple_pattern(pattern)?;
+            let tuples = prime_tuples(*s, *e, &offsets)?;
+
+            let table_data = &tuples
+                .iter()
+                .map(|p| {
+                    let members = offsets
+                        .iter()
+                        .map(|b| (p + b).to_string())
+                        .collect::<Vec<String>>();
+                    Matrix::new(format!("({})", members.join(", ")))
+                })
+                .collect::<Vec<Matrix>>();
+            matrix_print(
+                table_data,
+                format!(
+                    "Prime {}-tuples {:?}: {} found",
+                    offsets.len(),
+                    offsets,
+                    tuples.len()
+                ),
+                std::cmp::max(tuples.len() / 5, 1),
+            );
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("arith-table", matches)) => {
+            let s = matches.get_one::<u64>("start").expect("required");
+            let e = matches.get_one::<u64>("end").expect("required");
+            let k = matches.get_one::<u32>("k").expect("default");
+            let functions = matches
+                .get_many::<ArithmeticFunction>("functions")
+                .expect("default")
+                .cloned()
+                .collect::<Vec<ArithmeticFunction>>();
+
+            let table_data = arithmetic_table(*s, *e, *k)?
+                .into_iter()
+                .map(|(n, f)| ArithmeticTable::new(n, &f))
+                .collect::<Vec<ArithmeticTable>>();
+
+            let mut table = Table::new(&table_data);
+            table.with(Style::modern());
+            for (function, column) in [
+                (ArithmeticFunction::Phi, "φ(n)"),
+                (ArithmeticFunction::Mu, "μ(n)"),
+                (ArithmeticFunction::Sigma, "σₖ(n)"),
+                (ArithmeticFunction::Tau, "τ(n)"),
+                (ArithmeticFunction::Lambda, "λ(n)"),
+                (ArithmeticFunction::Omega, "ω(n)"),
+                (ArithmeticFunction::BigOmega, "Ω(n)"),
+            ] {
+                if !functions.contains(&function) {
+                    table.with(Disable::column(ByColumnName::new(column)));
+                }
+            }
+            println!("\nArithmetic Functions (k = {}):", k);
+            println!("{table}\n");
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("goldbach", matches)) => {
+            let (_, mut rng) =
+                seeded_rng(matches.get_one::<u64>("seed").copied().or(*session_seed));
+
+            let table_data = if let Some(n) = matches.get_one::<BigInt>("num") {
+                if n.is_odd() {
+                    let (p, q, r) = ternary_goldbach(n, &mut rng)?;
+                    println!("\n{} = {} + {} + {}\n", n, p, q, r);
+                    std::io::stdout().flush().map_err(|e| e.to_string())?;
+                    return Ok(false);
+                }
+                let (p, q) = goldbach_minimal(n, &mut rng)?;
+                // too large to count, only the minimal partition is shown
+                let count = n
+                    .to_u64()
+                    .and_then(|n| goldbach_count(n, &mut rng).ok())
+                    .map_or("-".to_string(), |count| count.to_string());
+                vec![GoldbachTable::new(
+                    n.to_string(),
+                    count,
+                    format!("{} + {}", p, q),
+                )]
+            } else {
+                let s = matches.get_one::<u64>("start").expect("required");
+                let e = matches.get_one::<u64>("end").expect("required");
+                (std::cmp::max(*s, 4)..=*e)
+                    .filter(|n| n % 2 == 0)
+                    .map(|n| {
+                        let (p, q) = goldbach_minimal(&BigInt::from(n), &mut rng)?;
+                        Ok(GoldbachTable::new(
+                            n.to_string(),
+                            goldbach_count(n, &mut rng)?.to_string(),
+                            format!("{} + {}", p, q),
+                        ))
+                    })
+                    .collect::<Result<Vec<GoldbachTable>, String>>()?
+            };
+
+            let mut table = Table::new(&table_data);
+            table.with(Style::modern());
+            println!("\nGoldbach Partitions n = p + q:");
+            println!("{table}\n");
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("squares", matches)) => {
+            let (seed, mut rng) =
+                seeded_rng(matches.get_one::<u64>("seed").copied().or(*session_seed));
+
+            let table_data = if let Some(n) = matches.get_one::<BigInt>("num") {
+                if n < &BigInt::one() {
+                    return Err("error: NUM must be positive\n".to_string());
+                }
+                let (factors, unfactored) = factorise(n, &mut rng);
+                if !unfactored.is_empty() {
+                    return Err(format!("error: {} could not be factored\n", n));
+                }
+                vec![SumsOfSquares::new(n, &factors, &mut rng)]
+            } else {
+                let s = matches.get_one::<u64>("start").expect("required");
+                let e = matches.get_one::<u64>("end").expect("required");
+                sums_of_squares_table(*s, *e, &mut rng)?
+            }
+            .iter()
+            .map(SquaresTable::new)
+            .collect::<Vec<SquaresTable>>();
+
+            let mut table = Table::new(&table_data);
+            table.with(Style::modern());
+            println!("\nSums of Squares:");
+            println!("{table}");
+            println!("Seed: {}\n", seed);
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
         Some(("composites-pq", matches)) => {
             let s = matches.get_one::<BigInt>("start").expect("required");
             let e = matches.get_one::<BigInt>("end").expect("required");
@@ -205,8 +502,20 @@ fn respond(line: &str) -> Result<bool, String> {
                     }
                 }
             } else {
-                let mut primes = vec![BigInt::from(2u64)];
-                println!("{:?}", n1.prime_factors(&mut primes));
+                let (_, mut rng) =
+                    seeded_rng(matches.get_one::<u64>("seed").copied().or(*session_seed));
+                if n1 < &BigInt::zero() {
+                    return Err("error: NUM1 must not be negative\n".to_string());
+                }
+                let mut table_data: Vec<NumFactorTable> = Vec::new();
+                let (factors, unfactored) = factorise(n1, &mut rng);
+                format_prime_factors_print(n1, &factors, &mut String::new(), &mut table_data);
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("\n{table}\n");
+                for m in unfactored {
+                    println!("{} is composite and was left unfactored\n", m);
+                }
             }
 
             //std::io::stdout().flush().map_err(|e| e.to_string())?;
@@ -216,6 +525,15 @@ fn respond(line: &str) -> Result<bool, String> {
                 .get_one::<PrimalityMethods>("method")
                 .expect("required");
             let n = matches.get_one::<BigInt>("num").expect("required");
+            let bases = matches
+                .get_many::<BigInt>("bases")
+                .map(|bases| bases.cloned().collect::<Vec<BigInt>>());
+            let rounds = matches.get_one::<u8>("rounds").expect("default");
+            let (seed, mut rng) =
+                seeded_rng(matches.get_one::<u64>("seed").copied().or(*session_seed));
+            // bases passed on the command line are used as is, else they are drawn at random
+            let random = bases.is_none();
+            let bases = bases.unwrap_or_else(|| random_bases(&mut rng, n, *rounds));
             match method {
                 PrimalityMethods::TrialDivision => {
                     if is_prime_trial_division_parallel(n) {
@@ -225,10 +543,24 @@ fn respond(line: &str) -> Result<bool, String> {
                     }
                 }
                 PrimalityMethods::Fermat => {
-                    println!("Fermat Primality Test - Not Implemented!");
+                    let (is_prime, table_data) = fermat_test(n, &bases);
+                    if !table_data.is_empty() {
+                        fermat_output_print(&table_data);
+                    }
+                    if random {
+                        println!("Seed: {}", seed);
+                    }
+                    match is_prime {
+                        Some(true) => println!("Fermat Test: {} is Probably Prime", n),
+                        Some(false) => println!("Fermat Test: {} is Composite", n),
+                        None => {
+                            println!("Fermat Test: inconclusive, every base is ≡ 0 (mod {})", n)
+                        }
+                    }
                 }
                 PrimalityMethods::Gcd => {
-                    let res = gcd_test(n, 5);
+                    let res = gcd_test(n, 5, &mut rng);
+                    println!("Seed: {}", seed);
                     let mut composite = false;
                     for i in res.iter() {
                         if i.1 > BigInt::one() {
@@ -241,15 +573,98 @@ fn respond(line: &str) -> Result<bool, String> {
                     }
                 }
                 PrimalityMethods::MillerRabin => {
-                    if miller_rabin_primality(n) {
-                        println!("{} is Probably Prime", n);
+                    // n - 1 = m.2ˢ needs an odd n >= 3, the rest is decided without bases
+                    if !random && n > &BigInt::from(2u64) && !n.is_even() {
+                        let mut table_data: Vec<MillerRabinTable> = Vec::new();
+                        let mut is_prime = true;
+                        for a in bases.iter() {
+                            let (res, steps) = miller_rabin_test(n, a);
+                            is_prime &= res;
+                            table_data.extend(steps);
+                        }
+                        miller_rabin_output_print(&table_data);
+                        if is_prime {
+                            println!("{} is Probably Prime", n);
+                        } else {
+                            println!("{} is Definitely Composite", n);
+                        }
+                    } else {
+                        if miller_rabin_deterministic_bases(n).is_none() {
+                            println!("Seed: {}", seed);
+                        }
+                        match miller_rabin(n, *rounds, &mut rng) {
+                            MillerRabinVerdict::PrimeDeterministic => {
+                                println!("{} is Prime (deterministic)", n);
+                            }
+                            MillerRabinVerdict::ProbablyPrime(rounds) => {
+                                println!("{} is Probably Prime ({} random rounds)", n, rounds);
+                            }
+                            MillerRabinVerdict::Composite => {
+                                println!("{} is Definitely Composite", n);
+                            }
+                        }
+                    }
+                }
+                PrimalityMethods::SolovayStrassen => {
+                    let (is_prime, table_data) = solovay_strassen(n, &bases);
+                    if !table_data.is_empty() {
+                        solovay_strassen_output_print(&table_data);
+                    }
+                    if random {
+                        println!("Seed: {}", seed);
+                    }
+                    match is_prime {
+                        Some(true) => println!("Solovay-Strassen Test: {} is Probably Prime", n),
+                        Some(false) => println!("Solovay-Strassen Test: {} is Composite", n),
+                        None => println!(
+                            "Solovay-Strassen Test: inconclusive, every base is ≡ 0 (mod {})",
+                            n
+                        ),
+                    }
+                }
+                PrimalityMethods::BailliePsw => {
+                    if baillie_psw(n) {
+                        println!("Baillie-PSW Test: {} is Prime", n);
                     } else {
-                        println!("{} is Definitely Composite", n);
+                        println!("Baillie-PSW Test: {} is Composite", n);
                     }
                 }
                 PrimalityMethods::AKS => {
-                    if aks(n).0 {
+                    let trace = aks(n);
+                    let table_data = trace
+                        .timings
+                        .iter()
+                        .map(|(step, elapsed)| {
+                            let outcome = match step {
+                                AksSteps::Step0 => "Failed".to_string(),
+                                AksSteps::Step2 => match (trace.r, trace.order) {
+                                    (Some(r), Some(order)) => {
+                                        format!("r = {}, ordᵣ(n) = {}", r, order)
+                                    }
+                                    _ => "Failed, no r found".to_string(),
+                                },
+                                _ if *step == trace.step => match &trace.a {
+                                    Some(a) => format!("Failed, a = {}", a),
+                                    None => "Prime".to_string(),
+                                },
+                                _ => "Passed".to_string(),
+                            };
+                            AksTable::new(
+                                format!("{:?}", step),
+                                step.description().to_string(),
+                                outcome,
+                                format!("{:.2?}", elapsed),
+                            )
+                        })
+                        .collect::<Vec<AksTable>>();
+                    let mut table = Table::new(&table_data);
+                    table.with(Style::modern());
+                    println!("\nAKS Primality Test:");
+                    println!("{table}\n");
+                    if trace.is_prime {
                         println!("{} is Prime", n);
+                    } else if trace.step == AksSteps::Step0 {
+                        println!("{} is neither Prime nor Composite", n);
                     } else {
                         println!("{} is Composite", n);
                     }
@@ -277,15 +692,428 @@ fn respond(line: &str) -> Result<bool, String> {
             println!("{table}\n");
             std::io::stdout().flush().map_err(|e| e.to_string())?;
         }
+        Some(("euler-jacobi-liars", matches)) => {
+            let n = matches.get_one::<BigInt>("num").expect("required");
+            let mut primes = vec![BigInt::from(2u64)];
+            let p_factors = n.prime_factors(&mut primes);
+            let non_witnesses = find_euler_jacobi_liars(n);
+            // Convert prime factors to String format
+            let mut form = String::new();
+            for (factor, exp) in p_factors {
+                form.push_str(&format!("{}{} x ", factor, Superscript(exp)));
+            }
+            let mut form = form.trim_end().to_string();
+            form.pop();
+            let json = json!(&non_witnesses);
+            let mut table = json_to_table(&json).into_table();
+
+            table.with(Style::modern()).with(Split::row(5).concat());
+            println!("\nEuler-Jacobi Liars for {} = {}", n, form);
+            println!("{table}\n");
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
         Some(("quadratic-sieve", matches)) => {
             let n = matches.get_one::<BigInt>("NUM").expect("required");
-            prepare_matrix(&n);
+            if n < &BigInt::from(4u64) || baillie_psw(n) {
+                return Err(format!("error: {} is not composite\n", n));
+            }
+            let trace = quadratic_sieve(n, matches.get_one::<u64>("bound").copied());
+            println!("\nB = {}, ⌊√n⌋ = {}", trace.bound, n.sqrt());
+
+            if !trace.factor_base.is_empty() {
+                let mut table = Table::new(&trace.factor_base);
+                table.with(Style::modern());
+                println!("\nFactor Base, -1 and the primes p <= B with (n/p) = 1:");
+                println!("{table}");
+            }
+            if !trace.relations.is_empty() {
+                let mut table = Table::new(&trace.relations);
+                table.with(Style::modern());
+                // one digit per prime, unreadable for a large factor base
+                if trace.factor_base.len() > 30 {
+                    table.with(Disable::column(ByColumnName::new("exponents mod 2")));
+                }
+                println!(
+                    "\nRelations t² ≡ y (mod n), y smooth over the factor base ({} values of t sieved):",
+                    trace.sieved
+                );
+                println!("{table}");
+            }
+            if !trace.dependencies.is_empty() {
+                let mut table = Table::new(&trace.dependencies);
+                table.with(Style::modern());
+                println!("\nDependencies over GF(2), X² ≡ Y² (mod n):");
+                println!("{table}");
+            }
+            match trace.factor {
+                Some(d) => println!("{} = {} x {}\n", n, d, n / &d),
+                None => println!(
+                    "No factor of {} found, every X ≡ ±Y (mod n); n may be a prime power\n",
+                    n
+                ),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("siqs", matches)) => {
+            let n = matches.get_one::<BigInt>("NUM").expect("required");
+            if n < &BigInt::from(4u64) || baillie_psw(n) {
+                return Err(format!("error: {} is not composite\n", n));
+            }
+            let (seed, mut rng) =
+                seeded_rng(matches.get_one::<u64>("seed").copied().or(*session_seed));
+            let checkpoint = matches.get_one::<String>("checkpoint").map(|s| s.as_str());
+            let fb_size = matches.get_one::<usize>("fb-size").copied();
+            println!();
+            let trace = siqs(n, fb_size, checkpoint, &mut rng, |progress| {
+                print!(
+                    "\r{} full + {} combined / {} relations, {} partials, {} polynomials, {:.1}s",
+                    progress.full,
+                    progress.combined,
+                    progress.needed,
+                    progress.partials,
+                    progress.polynomials,
+                    progress.elapsed.as_secs_f64()
+                );
+                let _ = std::io::stdout().flush();
+            })?;
+            println!(
+                "\n\nk = {}, factor base: {} primes up to {}, M = {}, A = q₁...q{}, large primes < {}, Seed: {}",
+                trace.multiplier,
+                trace.factor_base,
+                trace.largest_prime,
+                trace.m,
+                Subscript(trace.a_primes),
+                trace.large_prime_bound,
+                seed
+            );
+            if trace.resumed > 0 {
+                println!("{} relations read from the checkpoint", trace.resumed);
+            }
+            if let Some(matrix) = &trace.matrix {
+                println!(
+                    "Matrix: {} x {}, {} x {} after filtering, {} dependencies by {}",
+                    matrix.rows,
+                    matrix.columns,
+                    matrix.active_rows,
+                    matrix.active_columns,
+                    matrix.dependencies.len(),
+                    matrix.solver
+                );
+            }
+            if !trace.dependencies.is_empty() {
+                let mut table = Table::new(&trace.dependencies);
+                table.with(Style::modern());
+                println!("\nDependencies over GF(2), X² ≡ Y² (mod n):");
+                println!("{table}");
+            }
+            match trace.factor {
+                Some(d) => println!("{} = {} x {}\n", n, d, n / &d),
+                None => println!(
+                    "No factor of {} found, every X ≡ ±Y (mod n); n may be a prime power\n",
+                    n
+                ),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("gf2-nullspace", matches)) => {
+            let path = matches.get_one::<String>("file").expect("required");
+            let relations = read_relations(path)?;
+            let nullspace = relations.matrix.nullspace();
+            println!(
+                "\nMatrix: {} x {}, {} singletons and {} rows of cliques removed, {} x {} left",
+                nullspace.rows,
+                nullspace.columns,
+                nullspace.singletons,
+                nullspace.cliques,
+                nullspace.active_rows,
+                nullspace.active_columns
+            );
+            match nullspace.iterations {
+                0 => println!("{}", nullspace.solver),
+                k => println!("{}, {} iterations", nullspace.solver, k),
+            }
+
+            // the lines of the file behind each dependency, a pair of partials gives two
+            let lines = nullspace
+                .dependencies
+                .iter()
+                .map(|dependency| {
+                    let mut lines = dependency
+                        .iter()
+                        .flat_map(|&r| relations.lines[r].iter().copied())
+                        .collect::<Vec<usize>>();
+                    lines.sort();
+                    lines
+                })
+                .collect::<Vec<Vec<usize>>>();
+            let table_data = lines
+                .iter()
+                .enumerate()
+                .map(|(d, lines)| {
+                    let mut shown = lines
+                        .iter()
+                        .take(12)
+                        .map(|l| l.to_string())
+                        .collect::<Vec<String>>();
+                    if lines.len() > 12 {
+                        shown.push("...".to_string());
+                    }
+                    Gf2DependencyTable::new(
+                        (d + 1).to_string(),
+                        nullspace.dependencies[d].len().to_string(),
+                        shown.join(" "),
+                    )
+                })
+                .collect::<Vec<Gf2DependencyTable>>();
+            if table_data.is_empty() {
+                println!("No dependencies found\n");
+            } else {
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("\nDependencies, the lines of {} summing to 0:", path);
+                println!("{table}");
+            }
+            if let Some(output) = matches.get_one::<String>("output") {
+                let text = lines
+                    .iter()
+                    .map(|lines| {
+                        let lines = lines.iter().map(|l| l.to_string()).collect::<Vec<String>>();
+                        lines.join(" ") + "\n"
+                    })
+                    .collect::<String>();
+                std::fs::write(output, text).map_err(|e| format!("error: {}: {}\n", output, e))?;
+                println!("{} dependencies written to {}", lines.len(), output);
+            }
+            println!();
             std::io::stdout().flush().map_err(|e| e.to_string())?;
         }
         Some(("pollards-p-minus-1", matches)) => {
             let n = matches.get_one::<BigInt>("NUM").expect("required");
-            let b = matches.get_one::<BigInt>("BASE").expect("required");
-            pollards_p_1(n, b);
+            let b = matches.get_one::<BigInt>("BASE").expect("default");
+            if n < &BigInt::from(2u64) {
+                return Err("error: NUM must be at least 2\n".to_string());
+            }
+            if trivial_split(n).is_none() && b.mod_floor(n).is_zero() {
+                return Err("error: BASE must not be ≡ 0 (mod NUM)\n".to_string());
+            }
+            let (b1, b2) = match matches.get_one::<u64>("b1") {
+                Some(&b1) => (b1, b1.saturating_mul(100)),
+                None => p_1_bounds(n),
+            };
+            let b2 = matches.get_one::<u64>("b2").copied().unwrap_or(b2);
+            let (factors, table_data) = pollards_p_1(n, b, b1, b2);
+            if !table_data.is_empty() {
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("\n{table}");
+            }
+            println!("B1 = {}, B2 = {}", b1, b2);
+            match factors {
+                Some((p, q)) => println!("{} = {} x {}\n", n, p, q),
+                None if baillie_psw(n) => println!("{} is a prime\n", n),
+                None => println!("No factor of {} found, p - 1 is not B1, B2 smooth\n", n),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("williams-p-plus-1", matches)) => {
+            let n = matches.get_one::<BigInt>("NUM").expect("required");
+            let a = matches.get_one::<BigInt>("A").expect("default");
+            if n < &BigInt::from(2u64) {
+                return Err("error: NUM must be at least 2\n".to_string());
+            }
+            let (b1, b2) = match matches.get_one::<u64>("b1") {
+                Some(&b1) => (b1, b1.saturating_mul(100)),
+                None => p_1_bounds(n),
+            };
+            let b2 = matches.get_one::<u64>("b2").copied().unwrap_or(b2);
+            let (factors, table_data) = williams_p_plus_1(n, a, b1, b2);
+            if !table_data.is_empty() {
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("\n{table}");
+            }
+            println!("B1 = {}, B2 = {}", b1, b2);
+            match factors {
+                Some((p, q)) => println!("{} = {} x {}\n", n, p, q),
+                None if baillie_psw(n) => println!("{} is a prime\n", n),
+                None => println!("No factor of {} found, p ± 1 is not B1, B2 smooth\n", n),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("pollards-rho-factor", matches)) => {
+            let n = matches.get_one::<BigInt>("NUM").expect("required");
+            if n < &BigInt::from(2u64) {
+                return Err("error: NUM must be at least 2\n".to_string());
+            }
+            let (seed, mut rng) =
+                seeded_rng(matches.get_one::<u64>("seed").copied().or(*session_seed));
+            let (factor, table_data) = pollards_rho_brent(n, &mut rng);
+            if !table_data.is_empty() {
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("\n{table}");
+            }
+            println!("Seed: {}", seed);
+            match factor {
+                Some(d) => println!("{} = {} x {}\n", n, d, n / &d),
+                None if baillie_psw(n) => println!("{} is a prime\n", n),
+                None => println!("No factor of {} found, try another seed\n", n),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("fermat-factor", matches)) => {
+            let n = matches.get_one::<BigInt>("NUM").expect("required");
+            if n < &BigInt::from(2u64) {
+                return Err("error: NUM must be at least 2\n".to_string());
+            }
+            let k = *matches.get_one::<u64>("multiplier").expect("default");
+            let max_steps = *matches.get_one::<u64>("max-steps").expect("default");
+            if k == 0 {
+                return Err("error: the multiplier k must be positive\n".to_string());
+            }
+            let (factor, table_data) = fermat(n, k, max_steps);
+            if !table_data.is_empty() {
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("\n{table}");
+            }
+            match factor {
+                Some(d) => println!("{} = {} x {}\n", n, d, n / &d),
+                None if baillie_psw(n) => println!("{} is a prime\n", n),
+                None => println!(
+                    "No factor of {} found in {} steps, try a multiplier\n",
+                    n, max_steps
+                ),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("lehman-factor", matches)) => {
+            let n = matches.get_one::<BigInt>("NUM").expect("required");
+            if n < &BigInt::from(2u64) {
+                return Err("error: NUM must be at least 2\n".to_string());
+            }
+            if n.bits() > 64 {
+                return Err(format!(
+                    "error: {} is above 2⁶⁴, too large for Lehman's method\n",
+                    n
+                ));
+            }
+            let (factor, table_data) = lehman(n);
+            if !table_data.is_empty() {
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("\n{table}");
+            }
+            match factor {
+                Some(d) => println!("{} = {} x {}\n", n, d, n / &d),
+                None if baillie_psw(n) => println!("{} is a prime\n", n),
+                None => println!("No factor of {} found\n", n),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("hart-factor", matches)) => {
+            let n = matches.get_one::<BigInt>("NUM").expect("required");
+            if n < &BigInt::from(2u64) {
+                return Err("error: NUM must be at least 2\n".to_string());
+            }
+            let max_steps = *matches.get_one::<u64>("max-steps").expect("default");
+            let (factor, table_data) = hart_one_line(n, max_steps);
+            if !table_data.is_empty() {
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("\n{table}");
+            }
+            match factor {
+                Some(d) => println!("{} = {} x {}\n", n, d, n / &d),
+                None if baillie_psw(n) => println!("{} is a prime\n", n),
+                None => println!("No factor of {} found in {} steps\n", n, max_steps),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("euler-factor", matches)) => {
+            let n = matches.get_one::<BigInt>("NUM").expect("required");
+            if n < &BigInt::one() {
+                return Err("error: NUM must be positive\n".to_string());
+            }
+            let (seed, mut rng) =
+                seeded_rng(matches.get_one::<u64>("seed").copied().or(*session_seed));
+            let euler = euler_factorisation(n, &mut rng)?;
+            if euler.searched {
+                println!("\nn - a² tried for a from ⌊√n⌋ down to √(n/2):");
+            } else {
+                println!(
+                    "\nFrom the factorisation of n, Cornacchia on each p ≡ 1 (mod 4) (Seed: {}):",
+                    seed
+                );
+            }
+            for (a, b) in &euler.representations {
+                println!("{} = {}² + {}²", n, a, b);
+            }
+            if !euler.pairs.is_empty() {
+                let mut table = Table::new(&euler.pairs);
+                table.with(Style::modern());
+                println!("\n{table}");
+            }
+            match (euler.factor, euler.representations.len()) {
+                (Some(d), _) => println!("{} = {} x {}\n", n, d, n / &d),
+                (None, 0) => println!(
+                    "{} is not a sum of two squares, a q ≡ 3 (mod 4) divides it to an odd power\n",
+                    n
+                ),
+                (None, _) => println!(
+                    "{} has a single representation, Euler's method needs two\n",
+                    n
+                ),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("squfof", matches)) => {
+            let n = matches.get_one::<BigInt>("NUM").expect("required");
+            if n < &BigInt::from(2u64) {
+                return Err("error: NUM must be at least 2\n".to_string());
+            }
+            if n.bits() > 64 {
+                return Err(format!("error: {} is above 2⁶⁴, too large for SQUFOF\n", n));
+            }
+            let (factor, table_data) = squfof(n);
+            if !table_data.is_empty() {
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("\nForward cycle to a square Q, reverse cycle until P repeats:");
+                println!("{table}");
+            }
+            match factor {
+                Some(d) => println!("{} = {} x {}\n", n, d, n / &d),
+                None if baillie_psw(n) => println!("{} is a prime\n", n),
+                None => println!("No factor of {} found, every multiplier gave up\n", n),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("ecm", matches)) => {
+            let n = matches.get_one::<BigInt>("NUM").expect("required");
+            if n < &BigInt::from(2u64) {
+                return Err("error: NUM must be at least 2\n".to_string());
+            }
+            let b1 = *matches.get_one::<u64>("b1").expect("default");
+            let b2 = matches
+                .get_one::<u64>("b2")
+                .copied()
+                .unwrap_or(b1.saturating_mul(100));
+            let curves = *matches.get_one::<usize>("curves").expect("default");
+            let (seed, mut rng) =
+                seeded_rng(matches.get_one::<u64>("seed").copied().or(*session_seed));
+            let (factor, table_data) = ecm(n, b1, b2, curves, &mut rng);
+            if !table_data.is_empty() {
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!("\n{table}");
+            }
+            println!("B1 = {}, B2 = {}, Seed: {}", b1, b2, seed);
+            match factor {
+                Some(d) => println!("{} = {} x {}\n", n, d, n / &d),
+                None if baillie_psw(n) => println!("{} is a prime\n", n),
+                None => println!("No factor of {} found on {} curves\n", n, curves),
+            }
             std::io::stdout().flush().map_err(|e| e.to_string())?;
         }
         Some(("pollards-rho", matches)) => {
@@ -305,8 +1133,12 @@ fn respond(line: &str) -> Result<bool, String> {
         }
         Some(("aks-findr", matches)) => {
             let n = matches.get_one::<BigInt>("NUM").expect("required");
-            let r = findr(n);
-            println!("\nAKS 'r' value for {} is = {}", n, r);
+            match aks_find_r(n) {
+                Some((r, order)) => {
+                    println!("\nAKS 'r' value for {} is = {}, ordᵣ(n) = {}", n, r, order)
+                }
+                None => println!("\nNo AKS 'r' value found for {}", n),
+            }
             std::io::stdout().flush().map_err(|e| e.to_string())?;
         }
         Some(("list-primitive-roots", matches)) => {
@@ -339,9 +1171,7 @@ fn respond(line: &str) -> Result<bool, String> {
             let end = matches.get_one::<BigInt>("end").expect("required");
 
             let mut result: Vec<PrimitiveRootsTable> = Vec::new();
-            let (primes_in_range, _) =
-                find_primes_in_range_trial_division_parallel(start.clone(), end.clone());
-            for p in primes_in_range.iter() {
+            for p in primes_in_range(start, end).iter() {
                 let primitive_roots = primitive_roots_trial_n_error(p);
                 let phi_phi_n = euler_totient_phi(&(p - BigInt::one()));
                 let row = PrimitiveRootsTable::new(
@@ -423,87 +1253,214 @@ fn respond(line: &str) -> Result<bool, String> {
             let start = matches.get_one::<BigInt>("start").expect("required");
             let end = matches.get_one::<BigInt>("end").expect("required");
 
-            let mut result: HashMap<String, Vec<String>> = HashMap::new();
             let composites = list_prime_factors_in_range(start, end, NumCategory::Composites).1;
             let aks_test_res = composites
                 .par_iter()
-                .map(|(num, _)| (num, aks(num)))
-                .map(|(num, (is_prime, step))| (num.clone(), is_prime, step))
-                .collect::<Vec<(BigInt, bool, AksSteps)>>();
-            for (num, is_prime, step) in aks_test_res.iter() {
-                match step {
-                    AksSteps::Step1 => {
-                        if !is_prime {
-                            if result.get("step1").is_some() {
-                                let s = result.get_mut("step1").unwrap();
-                                s.push(num.to_string());
-                            } else {
-                                result.insert("step1".to_string(), vec![num.to_string()]);
-                            }
-                        }
-                    }
-                    AksSteps::Step2 => {
-                        if !is_prime {
-                            if result.get("step2").is_some() {
-                                let s = result.get_mut("step2").unwrap();
-                                s.push(num.to_string());
-                            } else {
-                                result.insert("step2".to_string(), vec![num.to_string()]);
-                            }
-                        }
-                    }
-                    AksSteps::Step3 => {
-                        if !is_prime {
-                            if result.get("step3").is_some() {
-                                let s = result.get_mut("step3").unwrap();
-                                s.push(num.to_string());
-                            } else {
-                                result.insert("step3".to_string(), vec![num.to_string()]);
-                            }
-                        }
-                    }
-                    AksSteps::Step4 => {
-                        if !is_prime {
-                            if result.get("step4").is_some() {
-                                let s = result.get_mut("step4").unwrap();
-                                s.push(num.to_string());
-                            } else {
-                                result.insert("step4".to_string(), vec![num.to_string()]);
-                            }
-                        }
-                    }
-                    AksSteps::Step5 => {
-                        if !is_prime {
-                            if result.get("step5").is_some() {
-                                let s = result.get_mut("step5").unwrap();
-                                s.push(num.to_string());
-                            } else {
-                                result.insert("step5".to_string(), vec![num.to_string()]);
-                            }
-                        }
-                    }
-                    AksSteps::Success => {
-                        if *is_prime {
-                            if result.get("success").is_some() {
-                                let s = result.get_mut("success").unwrap();
-                                s.push(num.to_string());
-                            } else {
-                                result.insert("success".to_string(), vec![num.to_string()]);
-                            }
-                        }
-                    }
+                .map(|(num, _)| (num.clone(), aks(num)))
+                .collect::<Vec<(BigInt, AksTrace)>>();
+
+            for step in [
+                AksSteps::Step1,
+                AksSteps::Step2,
+                AksSteps::Step3,
+                AksSteps::Step4,
+                AksSteps::Step5,
+                AksSteps::Success,
+            ] {
+                let table_data = aks_test_res
+                    .iter()
+                    .filter(|(_, trace)| trace.step == step)
+                    .map(|(num, trace)| {
+                        let or_dash = |x: Option<String>| x.unwrap_or("-".to_string());
+                        AksFailedTable::new(
+                            num.to_string(),
+                            or_dash(trace.r.map(|r| r.to_string())),
+                            or_dash(trace.order.map(|o| o.to_string())),
+                            or_dash(trace.a.as_ref().map(|a| a.to_string())),
+                        )
+                    })
+                    .collect::<Vec<AksFailedTable>>();
+                if table_data.is_empty() {
+                    continue;
+                }
+
+                let mut table = Table::new(&table_data);
+                table.with(Style::modern());
+                println!(
+                    "\nNumbers below failed in {:?} of AKS Algm ({}):",
+                    step,
+                    step.description()
+                );
+                println!("{table}\n");
+            }
+
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("mersenne", matches)) => {
+            let start = matches.get_one::<BigInt>("start").expect("required");
+            let end = matches.get_one::<BigInt>("end").expect("required");
+            let k_max = matches.get_one::<u64>("k-max").expect("default");
+            let k_max = matches.get_flag("factors").then_some(*k_max);
+
+            let table_data = list_mersenne_numbers(start, end, k_max)?;
+            let mut table = Table::new(&table_data);
+            table.with(Style::modern());
+            println!("\nMersenne Numbers - Lucas-Lehmer Test:");
+            println!("{table}\n");
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("pepin", matches)) => {
+            let start = matches.get_one::<u32>("start").expect("required");
+            let end = matches.get_one::<u32>("end").expect("required");
+            if *end > PEPIN_MAX_M {
+                return Err(format!("error: END must be at most {}\n", PEPIN_MAX_M));
+            }
+
+            let mut table_data = (*start..=*end)
+                .into_par_iter()
+                .map(|m| {
+                    let is_prime = pepin(m).expect("m <= PEPIN_MAX_M");
+                    let f: BigInt = (BigInt::one() << (1u64 << m)) + 1;
+                    let digits = f.to_string().len();
+                    let f = if digits <= 40 {
+                        f.to_string()
+                    } else {
+                        format!("({} digits)", digits)
+                    };
+                    let result = if is_prime { "Prime" } else { "Composite" };
+                    // the congruence does not apply to F₀ = 3
+                    (
+                        m,
+                        PepinTable::new(m.to_string(), f, is_prime && m > 0, result.to_string()),
+                    )
+                })
+                .collect::<Vec<(u32, PepinTable)>>();
+            table_data.sort_by_key(|(m, _)| *m);
+            let table_data = table_data
+                .into_iter()
+                .map(|(_, row)| row)
+                .collect::<Vec<PepinTable>>();
+
+            let mut table = Table::new(&table_data);
+            table.with(Style::modern());
+            println!("\nFermat Numbers - Pépin's Test:");
+            println!("{table}\n");
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("proth", matches)) => {
+            let k = matches.get_one::<BigInt>("k").expect("required");
+            let n = matches.get_one::<u64>("n").expect("required");
+            let (is_prime, a) = proth(k, *n)?;
+            let big_n: BigInt = k * (BigInt::one() << *n) + 1;
+            println!("\nN = {}.2{} + 1 = {}", k, Superscript(*n), big_n);
+            match (is_prime, a) {
+                (true, Some(a)) => {
+                    println!("Certificate: {}^((N - 1)/2) ≡ -1 (mod N)", a);
+                    println!("Proth's Test: N is Prime\n");
                 }
+                (false, Some(a)) => {
+                    println!("({}/N) = -1 but {}^((N - 1)/2) ≢ -1 (mod N)", a, a);
+                    println!("Proth's Test: N is Composite\n");
+                }
+                _ => println!("Proth's Test: N is Composite\n"),
             }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("gen-prime", matches)) => {
+            let bits = matches.get_one::<u64>("bits").expect("required");
+            let count = matches.get_one::<usize>("count").expect("default");
+            let kind = if matches.get_flag("safe") {
+                PrimeKind::Safe
+            } else if matches.get_flag("strong") {
+                PrimeKind::Strong
+            } else if matches.get_flag("sophie-germain") {
+                PrimeKind::SophieGermain
+            } else {
+                PrimeKind::Any
+            };
+            let (seed, mut rng) =
+                seeded_rng(matches.get_one::<u64>("seed").copied().or(*session_seed));
 
-            for (k, v) in result.iter() {
-                let json = json!(v);
-                let mut table1 = json_to_table(&json).into_table();
+            let mut table_data = Vec::<GeneratedPrimeTable>::new();
+            let mut certificates = Vec::<Certificate>::new();
+            for i in 1..=*count {
+                let prime = if matches.get_flag("cert") {
+                    let (prime, certificate) = random_provable_prime(*bits, kind, &mut rng)
+                        .map_err(|e| format!("error: {}", e))?;
+                    certificates.push(certificate);
+                    prime
+                } else {
+                    random_prime(*bits, kind, &mut rng).map_err(|e| format!("error: {}", e))?
+                };
+                let structure = prime
+                    .structure
+                    .iter()
+                    .map(|(name, value)| format!("{} = {}", name, value))
+                    .collect::<Vec<String>>()
+                    .join("\n");
+                table_data.push(GeneratedPrimeTable::new(
+                    i.to_string(),
+                    prime.p.to_string(),
+                    prime.p.bits().to_string(),
+                    structure,
+                ));
+            }
 
-                table1.with(Style::modern()).with(Split::row(20).concat());
-                println!("\nNumbers below failed in {} of AKS Algm:", k);
-                println!("{table1}\n");
+            let mut table = Table::new(&table_data);
+            table.with(Style::modern());
+            println!("\n{:?} primes of {} bits:", kind, bits);
+            println!("{table}\n");
+            for (i, certificate) in certificates.iter().enumerate() {
+                let json = serde_json::to_string_pretty(certificate).map_err(|e| e.to_string())?;
+                println!("Certificate #{}:\n{}\n", i + 1, json);
             }
+            println!("Seed: {}\n", seed);
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("certify", matches)) => {
+            let n = matches.get_one::<BigInt>("num").expect("required");
+            let method = matches
+                .get_one::<CertificateMethods>("method")
+                .expect("default");
+
+            let certificate = match method {
+                CertificateMethods::Pratt => certify(n, false),
+                CertificateMethods::Pocklington => certify(n, true),
+            }
+            .map_err(|e| format!("error: {}", e))?;
+            let json = serde_json::to_string_pretty(&certificate).map_err(|e| e.to_string())?;
+            match matches.get_one::<String>("output") {
+                Some(path) => {
+                    std::fs::write(path, json).map_err(|e| format!("error: {}: {}\n", path, e))?;
+                    println!("\nCertificate for {} written to {}\n", n, path);
+                }
+                None => println!("\n{}\n", json),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("verify-cert", matches)) => {
+            let path = matches.get_one::<String>("file").expect("required");
+            let json =
+                std::fs::read_to_string(path).map_err(|e| format!("error: {}: {}\n", path, e))?;
+            let certificate: Certificate =
+                serde_json::from_str(&json).map_err(|e| format!("error: {}: {}\n", path, e))?;
 
+            match verify_certificate(&certificate) {
+                Ok(n) => println!("\nCertificate verified: {} is Prime\n", n),
+                Err(e) => print!("\nCertificate rejected: {}\n", e),
+            }
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+        Some(("seed", matches)) => {
+            if matches.get_flag("clear") {
+                *session_seed = None;
+            } else if let Some(seed) = matches.get_one::<u64>("VALUE") {
+                *session_seed = Some(*seed);
+            }
+            match session_seed {
+                Some(seed) => println!("\nSeed: {}\n", seed),
+                None => println!("\nSeed: not set, a fresh seed is drawn for every command\n"),
+            }
             std::io::stdout().flush().map_err(|e| e.to_string())?;
         }
         Some(("quit", _matches)) => {
@@ -522,6 +1479,19 @@ fn respond(line: &str) -> Result<bool, String> {
     Ok(false)
 }
 
+fn seed_from_args(mut args: impl Iterator<Item = String>) -> Result<Option<u64>, String> {
+    while let Some(arg) = args.next() {
+        if arg == "--seed" {
+            let seed = args
+                .next()
+                .and_then(|s| s.parse::<u64>().ok())
+                .ok_or("error: --seed expects a non-negative integer")?;
+            return Ok(Some(seed));
+        }
+    }
+    Ok(None)
+}
+
 fn readline() -> Result<String, String> {
     write!(std::io::stdout(), "nt-tools> ").map_err(|e| e.to_string())?;
     std::io::stdout().flush().map_err(|e| e.to_string())?;
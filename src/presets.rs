@@ -1,31 +1,31 @@
 use crate::{
     display::{
         format_prime_factors_print, matrix_print, miller_rabin_output_print, GcdTestTable, Matrix,
-        MillerRabinJson, MillerRabinTable, NumFactorTable,
+        MersenneTable, MillerRabinJson, MillerRabinTable, NumFactorTable,
     },
     groups_modulo_n,
     primality::{
-        gcd_test, is_prime_trial_division, is_prime_trial_division_parallel, miller_rabin_test,
+        gcd_test, is_prime_trial_division, is_prime_trial_division_parallel, lucas_lehmer,
+        mersenne_small_factor, miller_rabin_test, solovay_strassen,
     },
     prime_factors::PrimeFactors,
+    sieve::{primes_in_range, small_primes, PrimeStream, SIEVE_LIMIT},
 };
 use fmtastic::Superscript;
 use num_bigint::BigInt;
 use num_iter::{range, range_inclusive};
+use num_traits::ToPrimitive;
 
 use homedir::get_my_home;
-use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, seq::SliceRandom};
 use rayon::{
-    iter::{
-        IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelBridge,
-        ParallelIterator,
-    },
+    iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelBridge, ParallelIterator},
     result,
 };
 
 use std::io::{ErrorKind, Write};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     fs::{self, File},
 };
 use std::{
@@ -207,21 +207,21 @@ pub fn list_carmichael_nums(
     (output1, carmichael_nums)
 }
 
-pub fn gcd_test_range(start: &BigInt, end: &BigInt) {
+pub fn gcd_test_range(start: &BigInt, end: &BigInt, rng: &mut StdRng) {
     let pq_nums = list_prime_factors_in_range(start, end, NumCategory::Composites);
     let pq_nums = pq_nums.1;
 
     // This will randomly choose three numbers which are composites in the range given
     let selected_nums_pq = pq_nums
-        .choose_multiple(&mut rand::thread_rng(), 3)
+        .choose_multiple(rng, 3)
         .map(|x| x.clone())
         .collect::<Vec<(BigInt, Vec<(BigInt, usize)>)>>();
 
-    let mut result = Vec::<(BigInt, Vec<(BigInt, usize)>, Vec<(BigInt, BigInt)>)>::new();
-    selected_nums_pq
-        .par_iter()
-        .map(|n| (n.0.clone(), n.1.clone(), gcd_test(&n.0, 4)))
-        .collect_into_vec(&mut result);
+    // The trials are drawn in sequence so that the same seed gives the same table
+    let result = selected_nums_pq
+        .iter()
+        .map(|n| (n.0.clone(), n.1.clone(), gcd_test(&n.0, 4, rng)))
+        .collect::<Vec<(BigInt, Vec<(BigInt, usize)>, Vec<(BigInt, BigInt)>)>>();
 
     let mut table_data = Vec::<GcdTestTable>::new();
     for (num, p_factors, gcd_result) in result {
@@ -251,7 +251,7 @@ pub fn find_miller_rabin_liars(n: &BigInt) -> (String, Vec<String>) {
     let mut non_witnesses: Vec<String> = Vec::new();
     let mut n_minus_one_form = String::new();
     for base in range(BigInt::from(2u64), n - 1) {
-        let output = miller_rabin_test(&n, Some(&base));
+        let output = miller_rabin_test(n, &base);
         for item in output.1.iter() {
             if item.get_message().contains("Prime") {
                 non_witnesses.push(base.to_string());
@@ -264,6 +264,199 @@ pub fn find_miller_rabin_liars(n: &BigInt) -> (String, Vec<String>) {
     (n_minus_one_form, non_witnesses)
 }
 
+///
+/// Returns the Euler-Jacobi liars of `n`, i.e. the bases 2 <= a <= n-2 for which the
+/// Solovay-Strassen test declares the composite `n` probably prime
+///
+pub fn find_euler_jacobi_liars(n: &BigInt) -> Vec<String> {
+    let mut non_witnesses: Vec<String> = Vec::new();
+    for base in range(BigInt::from(2u64), n - 1) {
+        let output = solovay_strassen(n, std::slice::from_ref(&base));
+        if output.0 == Some(true) {
+            non_witnesses.push(base.to_string());
+        }
+    }
+    non_witnesses
+}
+
+///
+/// Runs the Lucas-Lehmer test on Mₚ = 2ᵖ - 1 for every prime exponent p in the range.
+/// If `k_max` is passed, composite Mₚ are searched for a factor of the form 2kp + 1 with
+/// k <= k_max.
+///
+pub fn list_mersenne_numbers(
+    start: &BigInt,
+    end: &BigInt,
+    k_max: Option<u64>,
+) -> Result<Vec<MersenneTable>, String> {
+    let exponents = primes_in_range(start, end);
+    let exponents = exponents
+        .iter()
+        .map(|p| {
+            p.to_u64()
+                .ok_or(format!("error: exponent {} is too large\n", p))
+        })
+        .collect::<Result<Vec<u64>, String>>()?;
+
+    let mut table_data = exponents
+        .par_iter()
+        .map(|p| {
+            let is_prime = lucas_lehmer(*p);
+            let factor = match k_max {
+                Some(k_max) if !is_prime => mersenne_small_factor(*p, k_max),
+                _ => None,
+            };
+            let m: BigInt = (BigInt::from(1u64) << *p) - 1;
+            let digits = m.to_string().len();
+            let m = if digits <= 40 {
+                m.to_string()
+            } else {
+                format!("({} digits)", digits)
+            };
+            let factor = match factor {
+                Some(q) => format!("{} (k = {})", q, (&q - 1) / (2 * p)),
+                None => "-".to_string(),
+            };
+            let result = if is_prime { "Prime" } else { "Composite" };
+            (
+                *p,
+                MersenneTable::new(p.to_string(), m, result.to_string(), factor),
+            )
+        })
+        .collect::<Vec<(u64, MersenneTable)>>();
+    table_data.sort_by_key(|(p, _)| *p);
+
+    Ok(table_data.into_iter().map(|(_, row)| row).collect())
+}
+
+///
+/// Gaps g = p' - p between consecutive primes p < p' in [start, end], streamed from the
+/// sieve. A record gap is larger than every gap before it in the range, so the last
+/// record is the maximal gap. The histogram maps each gap to how often it occurs.
+///
+pub struct PrimeGaps {
+    pub prime_count: u64,
+    /// (gap, p)
+    pub records: Vec<(u64, u64)>,
+    pub histogram: BTreeMap<u64, u64>,
+}
+
+pub fn prime_gaps(start: u64, end: u64) -> Result<PrimeGaps, String> {
+    if end >= SIEVE_LIMIT {
+        return Err(format!(
+            "error: END is beyond the sieve limit {}\n",
+            SIEVE_LIMIT
+        ));
+    }
+    let mut gaps = PrimeGaps {
+        prime_count: 0,
+        records: Vec::new(),
+        histogram: BTreeMap::new(),
+    };
+    let mut previous: Option<u64> = None;
+
+    for q in PrimeStream::new(start, end) {
+        gaps.prime_count += 1;
+        if let Some(p) = previous {
+            let gap = q - p;
+            *gaps.histogram.entry(gap).or_default() += 1;
+            if gaps.records.last().is_none_or(|(record, _)| gap > *record) {
+                gaps.records.push((gap, p));
+            }
+        }
+        previous = Some(q);
+    }
+    Ok(gaps)
+}
+
+///
+/// A pattern 0 = b₁ < b₂ < ... < bₖ is admissible when for every prime q <= k the bᵢ
+/// miss at least one residue class mod q. Otherwise one of p + bᵢ is always divisible
+/// by q and the pattern has at most one prime k-tuple.
+///
+pub fn is_admissible(offsets: &[u64]) -> Result<(), String> {
+    for q in small_primes(offsets.len() as u64 + 1) {
+        let residues = offsets.iter().map(|b| b % q).collect::<HashSet<u64>>();
+        if residues.len() as u64 == q {
+            return Err(format!(
+                "error: the pattern {:?} is not admissible, it covers every residue mod {}\n",
+                offsets, q
+            ));
+        }
+    }
+    Ok(())
+}
+
+///
+/// Offsets of a named constellation (twins, cousins, sexy) or of a comma separated
+/// admissible pattern such as 0,2,6,8.
+///
+pub fn tuple_pattern(pattern: &str) -> Result<Vec<u64>, String> {
+    let offsets = match pattern {
+        "twins" => vec![0, 2],
+        "cousins" => vec![0, 4],
+        "sexy" => vec![0, 6],
+        _ => pattern
+            .split(',')
+            .map(|b| {
+                b.trim()
+                    .parse::<u64>()
+                    .map_err(|_| format!("error: '{}' is not a pattern or an offset\n", b))
+            })
+            .collect::<Result<Vec<u64>, String>>()?,
+    };
+    if offsets.first() != Some(&0) || offsets.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(format!(
+            "error: the offsets {:?} must start at 0 and increase\n",
+            offsets
+        ));
+    }
+    is_admissible(&offsets)?;
+    Ok(offsets)
+}
+
+///
+/// Primes p in [start, end] with every p + bᵢ prime. The primes are streamed through a
+/// window covering the width of the pattern. END + the width must stay below the sieve
+/// limit 10¹⁶.
+///
+pub fn prime_tuples(start: u64, end: u64, offsets: &[u64]) -> Result<Vec<u64>, String> {
+    let width = *offsets.last().unwrap();
+    if end.saturating_add(width) >= SIEVE_LIMIT {
+        return Err(format!(
+            "error: END + {} is beyond the sieve limit {}\n",
+            width, SIEVE_LIMIT
+        ));
+    }
+    let mut window = VecDeque::<u64>::new();
+    let mut tuples = Vec::new();
+    let mut check = |window: &VecDeque<u64>| {
+        let p = window[0];
+        if p <= end
+            && offsets.iter().all(|b| {
+                p.checked_add(*b)
+                    .is_some_and(|q| window.binary_search(&q).is_ok())
+            })
+        {
+            tuples.push(p);
+        }
+    };
+
+    for q in PrimeStream::new(start, end.saturating_add(width)) {
+        // every prime up to p + width is in the window, so p can be decided
+        while window.front().is_some_and(|p| p.saturating_add(width) < q) {
+            check(&window);
+            window.pop_front();
+        }
+        window.push_back(q);
+    }
+    while !window.is_empty() {
+        check(&window);
+        window.pop_front();
+    }
+    Ok(tuples)
+}
+
 pub fn search_nums_with_primitive_roots(start: BigInt, end: BigInt) -> (Vec<String>, Vec<String>) {
     let mut nums_with_prim_roots: Vec<String> = Vec::new();
     let mut nums_without_no_prim_roots: Vec<String> = Vec::new();
@@ -327,6 +520,32 @@ mod tests {
         (start, end, primes_in_range)
     }
 
+    #[test]
+    fn test_prime_gaps() {
+        let gaps = prime_gaps(0, 100).unwrap();
+        assert_eq!(gaps.prime_count, 25);
+        assert_eq!(gaps.records, vec![(1, 2), (2, 3), (4, 7), (6, 23), (8, 89)]);
+        assert_eq!(gaps.histogram.get(&2), Some(&8));
+        assert_eq!(gaps.histogram.values().sum::<u64>(), 24);
+        assert!(prime_gaps(u64::MAX - 615, u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_prime_tuples() {
+        let twins = prime_tuples(0, 100, &tuple_pattern("twins").unwrap()).unwrap();
+        assert_eq!(twins, vec![3, 5, 11, 17, 29, 41, 59, 71]);
+        // the last member of the tuple may lie past the end of the range
+        assert_eq!(prime_tuples(70, 71, &[0, 2]), Ok(vec![71]));
+        assert!(prime_tuples(0, u64::MAX, &[0, 2]).is_err());
+
+        let quadruplets = prime_tuples(0, 200, &tuple_pattern("0,2,6,8").unwrap()).unwrap();
+        assert_eq!(quadruplets, vec![5, 11, 101, 191]);
+
+        assert!(tuple_pattern("0,2,4").is_err());
+        assert!(tuple_pattern("2,4").is_err());
+        assert!(tuple_pattern("0,2,6").is_ok());
+    }
+
     #[test]
     fn test_find_primes_in_range_trial_division_parallel() {
         let (start, end, primes_in_range) = test_data();
@@ -336,6 +555,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_euler_jacobi_liars() {
+        let liars = vec![
+            "9", "10", "12", "16", "17", "22", "29", "38", "53", "62", "69", "74", "75", "79",
+            "81", "82",
+        ];
+        assert_eq!(liars, find_euler_jacobi_liars(&BigInt::from(91u64)));
+    }
+
+    #[test]
+    fn test_list_mersenne_numbers() {
+        let table_data =
+            list_mersenne_numbers(&BigInt::from(2u64), &BigInt::from(13u64), Some(10)).unwrap();
+        let results = table_data
+            .iter()
+            .map(|row| row.get_result())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            results,
+            vec!["Prime", "Prime", "Prime", "Prime", "Composite", "Prime"]
+        );
+    }
+
     #[test]
     fn test_find_primes_in_range_trial_division() {
         let (start, end, primes_in_range) = test_data();
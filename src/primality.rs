@@ -1,15 +1,20 @@
 use crate::{
-    display::{format_miller_rabin_steps_print, MillerRabinTable},
+    display::{
+        format_miller_rabin_steps_print, FermatTable, MillerRabinTable, SolovayStrassenTable,
+    },
     groups_modulo_n::coprime_nums_less_than_n,
     groups_modulo_n::euler_totient_phi_counting_coprimes,
     prime_factors::PrimeFactors,
-    utils::{abs_log, fastpoly, generate_random_int_in_range, modular_pow, Gcd},
+    utils::{
+        abs_log, generate_random_int_in_range, jacobi_symbol, modular_pow, polynomial_pow_mod, Gcd,
+    },
 };
 use fmtastic::Superscript;
 use num_bigint::BigInt;
 use num_integer::Integer;
 use num_iter::{range, range_inclusive};
-use num_traits::{One, Pow, Zero};
+use num_traits::{One, Pow, ToPrimitive, Zero};
+use rand::rngs::StdRng;
 use rayon::prelude::*;
 
 ///
@@ -75,41 +80,72 @@ pub fn is_prime_trial_division_parallel(n: &BigInt) -> bool {
     }
 }
 
-/// Finds the next prime number >= n.  
-/// Time complexity: *expected* O(sqrt(n))
-/// # Examples
-/// ```
-/// use next_prime::next_prime;
-/// assert_eq!(next_prime(BigInt::from(2u64)), BigInt::from(2u64));
-/// assert_eq!(next_prime(BigInt::from(4u64)), BigInt::from(5u64));
-/// ```
-pub fn next_prime(n: &BigInt) -> BigInt {
-    let (zero, two) = (BigInt::zero(), BigInt::from(2u64));
-    if n <= &two {
-        return two;
-    }
-    let mut m = n.clone();
-    if &m % &two == zero {
-        m += 1;
-    }
-    while !is_prime_trial_division_parallel(&m) {
-        m += 2;
-    }
-    m
+///
+/// Miller-Rabin Verdict
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum MillerRabinVerdict {
+    Composite,
+    /// n is below a proven bound and passed every base of the deterministic base set
+    PrimeDeterministic,
+    /// n is above the proven bounds and passed the given number of random rounds
+    ProbablyPrime(u8),
+}
+
+/// Proven bounds for deterministic Miller-Rabin: every composite n below the bound fails
+/// the test for at least one of the first `k` primes (Jaeschke, Jiang & Deng, Sorenson &
+/// Webster). The last bound is about 3.3·10²⁴.
+const MILLER_RABIN_BOUNDS: [(&str, usize); 11] = [
+    ("2047", 1),
+    ("1373653", 2),
+    ("25326001", 3),
+    ("3215031751", 4),
+    ("2152302898747", 5),
+    ("3474749660383", 6),
+    ("341550071728321", 7),
+    ("3825123056546413051", 9),
+    ("18446744073709551616", 12),
+    ("318665857834031151167461", 12),
+    ("3317044064679887385961981", 13),
+];
+
+const MILLER_RABIN_PRIMES: [u64; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// Largest m tried by Pépin's test, F₁₆ has 19729 digits and takes about a minute.
+pub const PEPIN_MAX_M: u32 = 16;
+/// Largest n tried by Proth's test, N = k.2ⁿ + 1 then costs about as much as F₁₆.
+pub const PROTH_MAX_N: u64 = 1 << 16;
+
+///
+/// Returns the smallest proven deterministic base set for n, or None if n is above
+/// 3,317,044,064,679,887,385,961,981 and only random bases can be used.
+///
+pub fn miller_rabin_deterministic_bases(n: &BigInt) -> Option<Vec<BigInt>> {
+    MILLER_RABIN_BOUNDS
+        .iter()
+        .find(|(bound, _)| n < &bound.parse::<BigInt>().unwrap())
+        .map(|(_, k)| {
+            MILLER_RABIN_PRIMES[..*k]
+                .iter()
+                .map(|p| BigInt::from(*p))
+                .collect()
+        })
 }
 
 /// https://www.youtube.com/watch?v=SSpcBIM9Gb8
 /// Miller-Rabin Test Step-1
-/// It accepts an integer and returns a boolean value
+/// It accepts an integer and returns the verdict.
 /// 1. Express n - 1 as 2ᶠm
-pub fn miller_rabin_primality(n: &BigInt) -> bool {
-    let (zero, one, _two) = (BigInt::from(0u64), BigInt::from(1u64), BigInt::from(2u64));
+/// 2. Below 3.3·10²⁴ the proven deterministic base set is used, above it `rounds`
+///    random bases are drawn from `rng`.
+pub fn miller_rabin(n: &BigInt, rounds: u8, rng: &mut StdRng) -> MillerRabinVerdict {
+    let (zero, one, two) = (BigInt::from(0u64), BigInt::from(1u64), BigInt::from(2u64));
     let three = BigInt::from(3u64);
-    if n <= &one || n == &BigInt::from(4u64) {
-        return false;
+    if n <= &one || (n > &two && n.is_even()) {
+        return MillerRabinVerdict::Composite;
     }
     if n <= &three {
-        return true;
+        return MillerRabinVerdict::PrimeDeterministic;
     }
 
     let mut d: BigInt = n - &one;
@@ -119,27 +155,47 @@ pub fn miller_rabin_primality(n: &BigInt) -> bool {
     }
     // d = (n - 1) / 2ᶠ
 
-    for _ in 0..5 {
-        if miller_test(&d, n) == false {
+    if let Some(bases) = miller_rabin_deterministic_bases(n) {
+        for a in bases.iter() {
+            // a base which is a multiple of n tells nothing (only when n is a small prime)
+            if (a % n).is_zero() {
+                continue;
+            }
+            if !miller_test(&d, n, a) {
+                return MillerRabinVerdict::Composite;
+            }
+        }
+        return MillerRabinVerdict::PrimeDeterministic;
+    }
+
+    for _ in 0..rounds {
+        // Randomly generate a base: a such that 1 < a < n - 1
+        let a: BigInt = generate_random_int_in_range(rng, &two, &(n - 1));
+        if !miller_test(&d, n, &a) {
             // If miller-rabin test returns false once, the given integer
             // is not a prime
-            return false;
+            return MillerRabinVerdict::Composite;
         }
     }
 
-    true
+    MillerRabinVerdict::ProbablyPrime(rounds)
+}
+
+///
+/// Miller-Rabin Test with the default of 5 random rounds above the deterministic bounds.
+///
+pub fn miller_rabin_primality(n: &BigInt, rng: &mut StdRng) -> bool {
+    miller_rabin(n, 5, rng) != MillerRabinVerdict::Composite
 }
 
 /// Miller-Rabin Test - Step 2
 ///
-fn miller_test(d: &BigInt, n: &BigInt) -> bool {
+fn miller_test(d: &BigInt, n: &BigInt, a: &BigInt) -> bool {
     let (_zero, one, two) = (BigInt::from(0u64), BigInt::from(1u64), BigInt::from(2u64));
     let mut d = d.clone();
-    // Randomly generate a base: a such that 1 < a < n - 1
-    let a: BigInt = generate_random_int_in_range(&two, &(n - 1));
 
     // Calculate x ≡ a^d(mod n)
-    let mut x = modular_pow(&a, &d, n);
+    let mut x = modular_pow(a, &d, n);
 
     // if x ≡ ±1 (mod n), return true
     if x == one || x == n - 1 {
@@ -175,16 +231,17 @@ fn miller_test(d: &BigInt, n: &BigInt) -> bool {
 ///
 /// # Arguments
 /// * n: BigInt
-/// * base: Optional - if base is not passed, `a` is randomly generated in the range
-///         2 <= a <= n-2
+/// * a: BigInt - the base, use `utils::random_bases` for randomly generated bases in the
+///   range 2 <= a <= n-2
 ///
-pub fn miller_rabin_test(n: &BigInt, base: Option<&BigInt>) -> (bool, Vec<MillerRabinTable>) {
-    if n.is_even() {
-        println!("Miller-Rabin Primality Check is not defined for even numbers.");
-    }
+pub fn miller_rabin_test(n: &BigInt, a: &BigInt) -> (bool, Vec<MillerRabinTable>) {
     let mut table_data: Vec<MillerRabinTable> = Vec::new();
+    // n - 1 = m.2ˢ with s >= 1 needs an odd n >= 3
+    if n < &BigInt::from(3u64) || n.is_even() {
+        return (n == &BigInt::from(2u64), table_data);
+    }
     let _is_prime = false;
-    let (zero, one, two) = (BigInt::from(0u64), BigInt::from(1u64), BigInt::from(2u64));
+    let (zero, one) = (BigInt::from(0u64), BigInt::from(1u64));
     let n_minus_one: BigInt = n - 1;
     let mut m = n_minus_one.clone();
 
@@ -196,17 +253,8 @@ pub fn miller_rabin_test(n: &BigInt, base: Option<&BigInt>) -> (bool, Vec<Miller
 
     let n_minus_one_form = format!("{} = {}.2{}", n_minus_one, m, Superscript(s),);
 
-    let a: BigInt;
-    // If `base` is not passed, then randomly generate a base "a" such that 1 < a < n - 1
-    if let Some(base) = base {
-        a = base.clone();
-    } else {
-        a = generate_random_int_in_range(&two, &(n - 1));
-    }
-    // let a = BigInt::from(1003u64);
-
     // Calculate x ≡ aᵐ(mod n)
-    let mut x = modular_pow(&a, &m, n);
+    let mut x = modular_pow(a, &m, n);
 
     format_miller_rabin_steps_print(
         n.clone(),
@@ -233,7 +281,7 @@ pub fn miller_rabin_test(n: &BigInt, base: Option<&BigInt>) -> (bool, Vec<Miller
     while k <= s - 1 {
         // searching square-roots for 1 (mod n) other than ±1 (mod n)
         let e = &m * BigInt::from(2u64).pow(k);
-        x = modular_pow(&a, &e, n);
+        x = modular_pow(a, &e, n);
 
         format_miller_rabin_steps_print(
             n.clone(),
@@ -265,6 +313,419 @@ pub fn miller_rabin_test(n: &BigInt, base: Option<&BigInt>) -> (bool, Vec<Miller
     return (false, table_data);
 }
 
+///
+/// Fermat Primality Test - Returns whether a number is probably prime or not along
+/// with the steps for every base tried. Bases a ≡ 0 (mod n) are skipped, the verdict is
+/// None when no base was left to test.
+///
+/// By FLT, if n is prime then aⁿ⁻¹ ≡ 1 (mod n) for every a coprime to n. A base `a`
+/// with aⁿ⁻¹ ≢ 1 (mod n) is a Fermat witness and proves n composite. A composite n
+/// with aⁿ⁻¹ ≡ 1 (mod n) for the chosen `a` is a Fermat pseudoprime to the base `a`
+/// (`a` is a Fermat liar). Carmichael numbers fool the test for every coprime base.
+///
+/// # Arguments
+/// * n: BigInt
+/// * bases: the values of `a` to try, use `utils::random_bases` for randomly generated
+///   bases in the range 2 <= a <= n-2
+///
+pub fn fermat_test(n: &BigInt, bases: &[BigInt]) -> (Option<bool>, Vec<FermatTable>) {
+    let (one, three) = (BigInt::one(), BigInt::from(3u64));
+    let mut table_data: Vec<FermatTable> = Vec::new();
+    if n <= &three {
+        return (Some(n > &one), table_data);
+    }
+
+    let n_minus_one = n - &one;
+    let mut is_probable_prime = true;
+    let tested = bases.iter().filter(|a| !a.mod_floor(n).is_zero()).count();
+    for a in bases.iter() {
+        let a_mod_n = a.mod_floor(n);
+        let gcd = n.gcd_euclid(&a_mod_n);
+        // Calculate x ≡ aⁿ⁻¹ (mod n)
+        let x = modular_pow(&a_mod_n, &n_minus_one, n);
+        let message = if a_mod_n.is_zero() {
+            // gcd(0, n) = n says nothing about n
+            format!("{} ≡ 0 (mod {}), base skipped", a, n)
+        } else if gcd > one {
+            is_probable_prime = false;
+            format!("gcd({}, {}) = {}, {} is composite", a, n, gcd, n)
+        } else if x == one {
+            format!("{} is Probably Prime to the base {}", n, a)
+        } else {
+            is_probable_prime = false;
+            format!("{} is a Fermat witness, {} is composite", a, n)
+        };
+        table_data.push(FermatTable::new(
+            n.to_string(),
+            a.to_string(),
+            gcd.to_string(),
+            x.to_string(),
+            message,
+        ));
+    }
+
+    ((tested > 0).then_some(is_probable_prime), table_data)
+}
+
+///
+/// Solovay-Strassen (Euler-Jacobi) Primality Test - Returns whether a number is probably
+/// prime or not along with the steps for every base tried. Bases a ≡ 0 (mod n) are
+/// skipped, the verdict is None when no base was left to test.
+///
+/// By Euler's criterion, if n is an odd prime then a⁽ⁿ⁻¹⁾ᐟ² ≡ (a/n) (mod n) for every `a`
+/// coprime to n, where (a/n) is the Jacobi symbol. A base for which the congruence fails
+/// is an Euler witness. Unlike the Fermat test, at most half of the coprime bases are
+/// Euler-Jacobi liars for a composite n, so there is no analogue of Carmichael numbers.
+///
+/// # Arguments
+/// * n: BigInt
+/// * bases: the values of `a` to try, use `utils::random_bases` for randomly generated
+///   bases in the range 2 <= a <= n-2
+///
+pub fn solovay_strassen(n: &BigInt, bases: &[BigInt]) -> (Option<bool>, Vec<SolovayStrassenTable>) {
+    let (one, two, three) = (BigInt::one(), BigInt::from(2u64), BigInt::from(3u64));
+    let mut table_data: Vec<SolovayStrassenTable> = Vec::new();
+    if n <= &three {
+        return (Some(n > &one), table_data);
+    }
+    if n.is_even() {
+        return (Some(false), table_data);
+    }
+
+    let n_minus_one = n - &one;
+    let exp = &n_minus_one / &two;
+
+    let mut is_probable_prime = true;
+    let tested = bases.iter().filter(|a| !a.mod_floor(n).is_zero()).count();
+    for a in bases.iter() {
+        let a_mod_n = a.mod_floor(n);
+        let jacobi = jacobi_symbol(&a_mod_n, n);
+        // Calculate x ≡ a⁽ⁿ⁻¹⁾ᐟ² (mod n)
+        let x = modular_pow(&a_mod_n, &exp, n);
+        let message = if a_mod_n.is_zero() {
+            // (0/n) = 0 says nothing about n
+            format!("{} ≡ 0 (mod {}), base skipped", a, n)
+        } else if jacobi == 0 {
+            is_probable_prime = false;
+            format!("gcd({}, {}) > 1, {} is composite", a, n, n)
+        } else if (jacobi == 1 && x == one) || (jacobi == -1 && x == n_minus_one) {
+            format!("{} is Probably Prime to the base {}", n, a)
+        } else {
+            is_probable_prime = false;
+            format!("{} is an Euler witness, {} is composite", a, n)
+        };
+        table_data.push(SolovayStrassenTable::new(
+            n.to_string(),
+            a.to_string(),
+            jacobi.to_string(),
+            x.to_string(),
+            message,
+        ));
+    }
+
+    ((tested > 0).then_some(is_probable_prime), table_data)
+}
+
+///
+/// Strong probable prime test to a single base `a`.
+///
+/// Writes n - 1 = d.2ˢ with d odd and returns true if aᵈ ≡ 1 (mod n) or
+/// a^(d.2ʳ) ≡ -1 (mod n) for some 0 <= r < s. The base is fixed, so the answer is
+/// reproducible, unlike `miller_rabin_primality`.
+///
+pub fn strong_probable_prime(n: &BigInt, a: &BigInt) -> bool {
+    let (one, two) = (BigInt::one(), BigInt::from(2u64));
+    if n <= &one {
+        return false;
+    }
+    if n == &two {
+        return true;
+    }
+    if n.is_even() {
+        return false;
+    }
+
+    let n_minus_one = n - &one;
+    let mut d = n_minus_one.clone();
+    let mut s = 0u64;
+    while d.is_even() {
+        d /= 2;
+        s += 1;
+    }
+
+    let a = a.mod_floor(n);
+    if a.is_zero() {
+        return true;
+    }
+    let mut x = modular_pow(&a, &d, n);
+    if x == one || x == n_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = (&x * &x) % n;
+        if x == n_minus_one {
+            return true;
+        }
+        if x == one {
+            return false;
+        }
+    }
+
+    false
+}
+
+///
+/// Strong Lucas probable prime test with Selfridge's parameters (Method A).
+///
+/// D is the first element of 5, -7, 9, -11, ... with Jacobi symbol (D/n) = -1, P = 1 and
+/// Q = (1 - D)/4. Writing n + 1 = d.2ˢ with d odd, n is a strong Lucas probable prime if
+/// U_d ≡ 0 (mod n) or V_(d.2ʳ) ≡ 0 (mod n) for some 0 <= r < s.
+///
+pub fn strong_lucas_probable_prime(n: &BigInt) -> bool {
+    let (one, two) = (BigInt::one(), BigInt::from(2u64));
+    if n <= &one {
+        return false;
+    }
+    if n == &two {
+        return true;
+    }
+    if n.is_even() {
+        return false;
+    }
+    // (D/n) is never -1 for a perfect square, the search for D below would not end
+    if n.sqrt().pow(2u32) == *n {
+        return false;
+    }
+
+    // Selfridge's Method A to choose D
+    let mut d_abs = BigInt::from(5u64);
+    let mut sign = 1i8;
+    let d = loop {
+        let d = if sign > 0 {
+            d_abs.clone()
+        } else {
+            -d_abs.clone()
+        };
+        match jacobi_symbol(&d, n) {
+            -1 => break d,
+            0 if d_abs != *n => return false,
+            _ => {}
+        }
+        d_abs += 2;
+        sign = -sign;
+    };
+    let p = BigInt::one();
+    let q: BigInt = (&one - &d) / 4u32;
+
+    let mut k = n + &one;
+    let mut s = 0u64;
+    while k.is_even() {
+        k /= 2;
+        s += 1;
+    }
+
+    // Halving modulo n for odd n: x/2 ≡ (x + n)/2 when x is odd
+    let half = |x: BigInt| -> BigInt {
+        let x = if x.is_odd() { x + n } else { x };
+        (x / 2u32).mod_floor(n)
+    };
+
+    // Binary Lucas chain for U_k, V_k and Qᵏ from the most significant bit of k
+    let (mut u, mut v, mut q_k) = (BigInt::one(), p.clone(), q.mod_floor(n));
+    for i in (0..k.bits() - 1).rev() {
+        // doubling: U_2k = U_k.V_k, V_2k = V_k² - 2Qᵏ
+        u = (&u * &v).mod_floor(n);
+        v = (&v * &v - &q_k * 2u32).mod_floor(n);
+        q_k = (&q_k * &q_k).mod_floor(n);
+        if k.bit(i) {
+            // increment: U_(k+1) = (P.U_k + V_k)/2, V_(k+1) = (D.U_k + P.V_k)/2
+            let u_next = half(&p * &u + &v);
+            v = half(&d * &u + &p * &v);
+            u = u_next;
+            q_k = (&q_k * &q).mod_floor(n);
+        }
+    }
+
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = (&v * &v - &q_k * 2u32).mod_floor(n);
+        if v.is_zero() {
+            return true;
+        }
+        q_k = (&q_k * &q_k).mod_floor(n);
+    }
+
+    false
+}
+
+///
+/// Baillie-PSW Primality Test
+///
+/// A strong probable prime test to the base 2 followed by a strong Lucas probable prime
+/// test. No composite number is known to pass both and none exist below 2⁶⁴. It is
+/// deterministic, so the same number always gets the same answer.
+///
+pub fn baillie_psw(n: &BigInt) -> bool {
+    if n <= &BigInt::one() {
+        return false;
+    }
+    // trial division by the small primes settles the small cases quickly
+    for p in [2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        let p = BigInt::from(p);
+        if n == &p {
+            return true;
+        }
+        if (n % &p).is_zero() {
+            return false;
+        }
+    }
+
+    strong_probable_prime(n, &BigInt::from(2u64)) && strong_lucas_probable_prime(n)
+}
+
+///
+/// Reduces x modulo the Mersenne number m = 2ᵖ - 1 without division.
+///
+/// Since 2ᵖ ≡ 1 (mod m), the high bits of x can be folded onto the low bits:
+/// x ≡ (x mod 2ᵖ) + ⌊x / 2ᵖ⌋ (mod m).
+///
+pub fn mod_mersenne(x: &BigInt, p: u64, m: &BigInt) -> BigInt {
+    let mut x = x.clone();
+    while x.bits() > p {
+        x = (&x & m) + (&x >> p);
+    }
+    if &x == m {
+        BigInt::zero()
+    } else {
+        x
+    }
+}
+
+///
+/// Lucas-Lehmer Test for the Mersenne number Mₚ = 2ᵖ - 1
+///
+/// For an odd prime p, Mₚ is prime if and only if s₍ₚ₋₂₎ ≡ 0 (mod Mₚ) where s₀ = 4 and
+/// sᵢ = sᵢ₋₁² - 2. Mₚ is composite whenever p is composite, so only prime exponents
+/// are tested.
+///
+pub fn lucas_lehmer(p: u64) -> bool {
+    if p == 2 {
+        return true;
+    }
+    if !baillie_psw(&BigInt::from(p)) {
+        return false;
+    }
+
+    let m: BigInt = (BigInt::one() << p) - 1;
+    let mut s = BigInt::from(4u64);
+    for _ in 0..p - 2 {
+        // s ≥ 0 always, so s² - 2 < 0 only when s = 0 or 1
+        s = mod_mersenne(&(&s * &s + &m - 2u32), p, &m);
+    }
+
+    s.is_zero()
+}
+
+///
+/// Searches for a factor q = 2kp + 1 of Mₚ for 1 <= k <= k_max.
+///
+/// Every prime factor of Mₚ (p an odd prime) is of the form 2kp + 1 and is ≡ ±1 (mod 8),
+/// so the other candidates are skipped. q divides Mₚ exactly when 2ᵖ ≡ 1 (mod q).
+///
+pub fn mersenne_small_factor(p: u64, k_max: u64) -> Option<BigInt> {
+    let (one, two, eight) = (BigInt::one(), BigInt::from(2u64), BigInt::from(8u64));
+    let exponent = BigInt::from(p);
+    let m: BigInt = (BigInt::one() << p) - 1;
+    for k in 1..=k_max {
+        let q = BigInt::from(2 * k) * p + 1;
+        if q >= m {
+            break;
+        }
+        let r = &q % &eight;
+        if r != one && r != BigInt::from(7u64) {
+            continue;
+        }
+        if modular_pow(&two, &exponent, &q) == one {
+            return Some(q);
+        }
+    }
+
+    None
+}
+
+///
+/// Pépin's Test for the Fermat number Fₘ = 2^(2ᵐ) + 1
+///
+/// For m >= 1, Fₘ is prime if and only if 3^((Fₘ - 1)/2) ≡ -1 (mod Fₘ). Returns an
+/// error for m > PEPIN_MAX_M.
+///
+pub fn pepin(m: u32) -> Result<bool, String> {
+    if m > PEPIN_MAX_M {
+        return Err(format!(
+            "error: Pépin's test is limited to m <= {}, got m = {}\n",
+            PEPIN_MAX_M, m
+        ));
+    }
+    // F₀ = 3 is prime, but 3 is not coprime to it
+    if m == 0 {
+        return Ok(true);
+    }
+    let f: BigInt = (BigInt::one() << (1u64 << m)) + 1;
+    let exp: BigInt = (&f - 1u32) >> 1;
+
+    Ok(modular_pow(&BigInt::from(3u64), &exp, &f) == &f - 1u32)
+}
+
+///
+/// Proth's Theorem for N = k.2ⁿ + 1 with k odd and k < 2ⁿ
+///
+/// N is prime if and only if there is an `a` with a^((N-1)/2) ≡ -1 (mod N). The search
+/// tries a = 2, 3, 5, 6, ... with Jacobi symbol (a/N) = -1: if N is prime then
+/// a^((N-1)/2) ≡ (a/N) = -1 by Euler's criterion, so the first such `a` decides.
+///
+/// Returns whether N is prime and the base `a` that decided: a certificate of primality
+/// when N is prime, an Euler witness when it is composite. No base is returned when N is
+/// a perfect square or shares a factor with a small base. Returns an error for
+/// n > PROTH_MAX_N.
+///
+pub fn proth(k: &BigInt, n: u64) -> Result<(bool, Option<BigInt>), String> {
+    if n > PROTH_MAX_N {
+        return Err(format!(
+            "error: Proth's test is limited to n <= {}, got n = {}\n",
+            PROTH_MAX_N, n
+        ));
+    }
+    let one = BigInt::one();
+    let two_n: BigInt = BigInt::one() << n;
+    if k.is_even() || k < &one || k >= &two_n {
+        return Err(format!(
+            "error: Proth's theorem needs k odd and 0 < k < 2ⁿ, got k = {}, n = {}\n",
+            k, n
+        ));
+    }
+    let big_n = k * &two_n + &one;
+    let exp: BigInt = (&big_n - &one) >> 1;
+
+    // (a/N) is never -1 for a perfect square
+    if big_n.sqrt().pow(2u32) == big_n {
+        return Ok((false, None));
+    }
+
+    let mut a = BigInt::from(2u64);
+    loop {
+        match jacobi_symbol(&a, &big_n) {
+            0 => return Ok((false, None)),
+            -1 => {
+                let x = modular_pow(&a, &exp, &big_n);
+                return Ok((x == &big_n - &one, Some(a)));
+            }
+            _ => a += 1,
+        }
+    }
+}
+
 /// Returns a Vec of randomly selected `a` value and `gcd`
 ///
 /// # Arguments
@@ -274,13 +735,18 @@ pub fn miller_rabin_test(n: &BigInt, base: Option<&BigInt>) -> (bool, Vec<Miller
 /// # Examples
 /// ```
 /// use crate::primality::gcd_test
-/// let result: Vec<(BigInt, BigInt)> = gcd_test(&BigInt::from(2881u64), 4);
+/// let (_, mut rng) = seeded_rng(Some(42));
+/// let result: Vec<(BigInt, BigInt)> = gcd_test(&BigInt::from(2881u64), 4, &mut rng);
 /// ```
 ///
-pub fn gcd_test(n: &BigInt, num_trials: u8) -> Vec<(BigInt, BigInt)> {
+pub fn gcd_test(n: &BigInt, num_trials: u8, rng: &mut StdRng) -> Vec<(BigInt, BigInt)> {
     let mut r = Vec::<BigInt>::new();
     for _ in 0..num_trials {
-        r.push(generate_random_int_in_range(&BigInt::from(2u8), &(n - 1)));
+        r.push(generate_random_int_in_range(
+            rng,
+            &BigInt::from(2u8),
+            &(n - 1),
+        ));
     }
 
     let mut result = Vec::<(BigInt, BigInt)>::new();
@@ -341,8 +807,9 @@ pub fn carmichael_nums_korselt(n: &BigInt) -> bool {
 ///
 /// AKS Steps
 ///
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AksSteps {
+    Step0,
     Step1,
     Step2,
     Step3,
@@ -350,97 +817,185 @@ pub enum AksSteps {
     Step5,
     Success,
 }
+
+impl AksSteps {
+    pub fn description(&self) -> &'static str {
+        match self {
+            AksSteps::Step0 => "n < 2 is neither prime nor composite",
+            AksSteps::Step1 => "n = aᵇ for some b > 1",
+            AksSteps::Step2 => "smallest r with ordᵣ(n) > log₂²n",
+            AksSteps::Step3 => "1 < gcd(a, n) < n for some a <= r",
+            AksSteps::Step4 => "n <= r",
+            AksSteps::Step5 => "(x + a)ⁿ ≢ xⁿ + a (mod xʳ - 1, n) for some a <= ⌊√φ(r).log₂n⌋",
+            AksSteps::Success => "n is prime",
+        }
+    }
+}
+
 ///
-/// AKS Primality test
+/// Trace of a run of the AKS test: the step that decided the verdict, the r and
+/// ordᵣ(n) found in step 2, the base a that step 1 (a = the root), step 3 or step 5
+/// failed at, and the time spent in each step that was run.
 ///
-/// Returns boolean indicating prime or not and if the step at which it failed
+#[derive(Clone, Debug)]
+pub struct AksTrace {
+    pub is_prime: bool,
+    pub step: AksSteps,
+    pub r: Option<u64>,
+    pub order: Option<u64>,
+    pub a: Option<BigInt>,
+    pub timings: Vec<(AksSteps, std::time::Duration)>,
+}
+
 ///
-/// # Arguments
-/// * n: BigInt
+/// ⌊log₂²n⌋ + 1, the smallest order ordᵣ(n) accepted in step 2 of AKS
 ///
-pub fn aks(n: &BigInt) -> (bool, AksSteps) {
-    fn is_perfect_k_th_power(n: &BigInt) -> bool {
-        let upper_bound = n.sqrt();
-        for k in range_inclusive(BigInt::from(2u64), upper_bound) {
-            let mut m = n.clone();
-            let mut j = BigInt::zero();
-            while &m % &k == BigInt::zero() && m > BigInt::one() {
-                m /= &k;
-                j += 1;
-            }
-            if m == BigInt::one() && j > BigInt::one() {
-                return true;
-            }
-        }
-        false
+fn aks_min_order(n: &BigInt) -> Option<u64> {
+    let log2_n = abs_log(n).ok()? / std::f64::consts::LN_2;
+    Some((log2_n * log2_n).floor() as u64 + 1)
+}
+
+///
+/// Step 2 of AKS. Returns the smallest r coprime to n with ordᵣ(n) > log₂²n and that
+/// order, or None if there is no such r <= max(3, ⌈log₂n⌉⁵), which the AKS paper
+/// (Lemma 4.3) shows cannot happen, or for n < 2.
+///
+pub fn aks_find_r(n: &BigInt) -> Option<(u64, u64)> {
+    if n < &BigInt::from(2u64) {
+        return None;
     }
+    let min_order = aks_min_order(n)?;
+    let r_max = std::cmp::max(3, n.bits().saturating_pow(5));
 
-    ///
-    /// Find smallest r such that the order of n mod r > ln(n)^2.
-    ///
-    fn findr(n: &BigInt) -> BigInt {
-        let (zero, one) = (BigInt::zero(), BigInt::one());
-        let mut r = BigInt::from(1u64);
-
-        let s: f64 = abs_log(n).unwrap().pow(2);
-        let s = BigInt::from(s.floor() as u64);
-        let mut nex_r = true;
-
-        while nex_r {
-            r += 1;
-            nex_r = false;
-            let mut k = BigInt::zero();
-            while &k <= &s && nex_r == false {
-                k += 1;
-                if modular_pow(n, &k, &r) == zero || modular_pow(n, &k, &r) == one {
-                    nex_r = true;
-                }
-            }
+    (2..=r_max).find_map(|r| {
+        let n_mod_r = (n % r).to_u64().unwrap();
+        if n_mod_r.gcd(&r) != 1 {
+            return None;
+        }
+        // multiplicative order of n mod r
+        let (mut x, mut k) = (n_mod_r, 1u64);
+        while x != 1 {
+            x = ((x as u128 * n_mod_r as u128) % r as u128) as u64;
+            k += 1;
         }
+        (k >= min_order).then_some((r, k))
+    })
+}
 
-        r
+///
+/// AKS Primality test
+///
+/// Agrawal-Kayal-Saxena over the ring (Z/nZ)[x]/(xʳ - 1):
+/// 1. if n is a perfect power, n is composite
+/// 2. find the smallest r with ordᵣ(n) > log₂²n
+/// 3. if 1 < gcd(a, n) < n for some a <= r, n is composite
+/// 4. if n <= r, n is prime
+/// 5. if (x + a)ⁿ ≢ xⁿ + a (mod xʳ - 1, n) for some 1 <= a <= ⌊√φ(r).log₂n⌋,
+///    n is composite, else n is prime
+///
+/// Returns the verdict with the trace of the steps that were run.
+///
+/// # Arguments
+/// * n: BigInt, n >= 2
+///
+pub fn aks(n: &BigInt) -> AksTrace {
+    use std::time::Instant;
+
+    let mut trace = AksTrace {
+        is_prime: false,
+        step: AksSteps::Step1,
+        r: None,
+        order: None,
+        a: None,
+        timings: Vec::new(),
+    };
+    let finish = |mut trace: AksTrace, is_prime: bool, step: AksSteps, start: Instant| {
+        trace.timings.push((step, start.elapsed()));
+        trace.is_prime = is_prime;
+        trace.step = step;
+        trace
+    };
+
+    // Step 0, 0 and 1 have no r and would pass every later step
+    let start = Instant::now();
+    if n < &BigInt::from(2u64) {
+        return finish(trace, false, AksSteps::Step0, start);
     }
 
     // Step 1
-    if is_perfect_k_th_power(n) {
-        return (false, AksSteps::Step1);
+    let start = Instant::now();
+    let perfect_power = (2..=n.bits() as u32).find_map(|b| {
+        let root = n.nth_root(b);
+        (root > BigInt::one() && Pow::pow(&root, b) == *n).then_some(root)
+    });
+    if let Some(root) = perfect_power {
+        trace.a = Some(root);
+        return finish(trace, false, AksSteps::Step1, start);
     }
-
-    let one = BigInt::one();
+    trace.timings.push((AksSteps::Step1, start.elapsed()));
 
     // Step 2
-    let r = findr(n);
+    let start = Instant::now();
+    let Some((r, order)) = aks_find_r(n) else {
+        return finish(trace, false, AksSteps::Step2, start);
+    };
+    trace.r = Some(r);
+    trace.order = Some(order);
+    trace.timings.push((AksSteps::Step2, start.elapsed()));
 
     // Step 3
-    for a in range(BigInt::from(2u64), std::cmp::min(r.clone(), n.clone())) {
-        if &a.gcd_euclid(n) > &one {
-            return (false, AksSteps::Step3);
-        }
+    let start = Instant::now();
+    let a_max = if n <= &BigInt::from(r) {
+        n.to_u64().unwrap() - 1
+    } else {
+        r
+    };
+    if let Some(a) = (2..=a_max).find(|a| (n % a).to_u64().unwrap().gcd(a) > 1) {
+        trace.a = Some(BigInt::from(a));
+        return finish(trace, false, AksSteps::Step3, start);
     }
+    trace.timings.push((AksSteps::Step3, start.elapsed()));
 
     // Step 4
-    if n <= &r {
-        return (true, AksSteps::Step4);
+    let start = Instant::now();
+    if n <= &BigInt::from(r) {
+        return finish(trace, true, AksSteps::Step4, start);
     }
+    trace.timings.push((AksSteps::Step4, start.elapsed()));
 
     // Step 5
-    let phi_r = euler_totient_phi_counting_coprimes(&r);
-    let log_r = abs_log(n).unwrap();
-    let upper_bound = phi_r.sqrt() * log_r as u64;
-    let mut x = Vec::<BigInt>::new();
-    for a in range(BigInt::one(), upper_bound) {
-        x = fastpoly(&vec![a, BigInt::one()], &n, &r);
-        if x.par_iter().any(|b| b != &BigInt::zero()) {
-            return (false, AksSteps::Step5);
-        }
+    let start = Instant::now();
+    let phi_r = euler_totient_phi_counting_coprimes(&BigInt::from(r))
+        .to_f64()
+        .unwrap();
+    // n > r >= 2 here, so the log exists
+    let log2_n = abs_log(n).map_or(0.0, |ln_n| ln_n / std::f64::consts::LN_2);
+    let a_max = (phi_r.sqrt() * log2_n).floor() as u64;
+    let r = r as usize;
+    // xⁿ = x^(n mod r) in the ring
+    let n_mod_r = (n % r).to_usize().unwrap();
+    let failing_a = (1..=a_max).into_par_iter().find_first(|a| {
+        let a = BigInt::from(*a);
+        let mut x_plus_a = vec![BigInt::zero(); r];
+        x_plus_a[0] = &a % n;
+        x_plus_a[1] = BigInt::one();
+        let mut lhs = polynomial_pow_mod(&x_plus_a, n, n, r);
+        lhs[0] -= &a;
+        lhs[n_mod_r] -= 1;
+        lhs.iter().any(|c| !c.mod_floor(n).is_zero())
+    });
+    if let Some(a) = failing_a {
+        trace.a = Some(BigInt::from(a));
+        return finish(trace, false, AksSteps::Step5, start);
     }
 
-    (true, AksSteps::Success)
+    finish(trace, true, AksSteps::Success, start)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::{random_bases, seeded_rng};
 
     // #[test]
     // fn test_gcd_euclid_1() {
@@ -461,6 +1016,43 @@ mod tests {
     //     assert_eq!(result, s);
     // }
 
+    #[test]
+    fn test_aks() {
+        let trace = aks(&BigInt::from(2187u64));
+        assert_eq!((trace.is_prime, trace.step), (false, AksSteps::Step1));
+        assert_eq!(trace.a, Some(BigInt::from(3u64)));
+
+        let trace = aks(&BigInt::from(1001u64));
+        assert_eq!((trace.is_prime, trace.step), (false, AksSteps::Step3));
+        assert_eq!((trace.r, trace.order), (Some(103), Some(102)));
+        assert_eq!(trace.a, Some(BigInt::from(7u64)));
+
+        let trace = aks(&BigInt::from(7u64));
+        assert_eq!((trace.is_prime, trace.step), (true, AksSteps::Step4));
+
+        let trace = aks(&BigInt::from(211u64));
+        assert_eq!((trace.is_prime, trace.step), (true, AksSteps::Success));
+        assert_eq!(trace.timings.len(), 5);
+
+        // 1009 x 1013, both prime factors beyond r = 409
+        let trace = aks(&BigInt::from(1022117u64));
+        assert_eq!((trace.is_prime, trace.step), (false, AksSteps::Step5));
+        assert_eq!(trace.a, Some(BigInt::one()));
+
+        for n in [BigInt::zero(), BigInt::one()] {
+            let trace = aks(&n);
+            assert_eq!((trace.is_prime, trace.step), (false, AksSteps::Step0));
+        }
+    }
+
+    #[test]
+    fn test_aks_find_r() {
+        // log₂²71 ≈ 37.6, and 41 is the smallest r with ordᵣ(71) >= 38
+        assert_eq!(aks_find_r(&BigInt::from(71u64)), Some((41, 40)));
+        assert_eq!(aks_find_r(&BigInt::zero()), None);
+        assert_eq!(aks_find_r(&BigInt::one()), None);
+    }
+
     #[test]
     fn test_is_prime_1() {
         let result = is_prime_trial_division(&BigInt::from(409u64));
@@ -481,48 +1073,206 @@ mod tests {
 
     #[test]
     fn test_miller_rabin_primality_1() {
-        let result = miller_rabin_primality(&BigInt::from(409u64));
+        let (_, mut rng) = seeded_rng(Some(409));
+        let result = miller_rabin_primality(&BigInt::from(409u64), &mut rng);
         assert_eq!(result, true);
     }
 
     #[test]
     fn test_miller_rabin_primality_2() {
-        let result = miller_rabin_primality(&BigInt::from(511u64));
+        let (_, mut rng) = seeded_rng(Some(511));
+        let result = miller_rabin_primality(&BigInt::from(511u64), &mut rng);
         assert_eq!(result, false);
-        let result = miller_rabin_primality(&BigInt::from(721u64));
+        let result = miller_rabin_primality(&BigInt::from(721u64), &mut rng);
         assert_eq!(result, false);
-        let result = miller_rabin_primality(&"49675218696612399034240799519655205503986657506787162015105425670413948962864456158664793804627084299081036134562339483478437262146378569515417671690110863951848724044479367633926630234074394356492223".parse::<BigInt>().unwrap());
+        let result = miller_rabin_primality(&"49675218696612399034240799519655205503986657506787162015105425670413948962864456158664793804627084299081036134562339483478437262146378569515417671690110863951848724044479367633926630234074394356492223".parse::<BigInt>().unwrap(), &mut rng);
         assert_eq!(result, true);
     }
 
     #[test]
-    fn edge_case_two() {
-        assert_eq!(next_prime(&BigInt::from(2u64)), BigInt::from(2u64));
+    fn test_miller_rabin_deterministic() {
+        let (_, mut rng) = seeded_rng(Some(42));
+        assert_eq!(
+            miller_rabin(&BigInt::from(409u64), 5, &mut rng),
+            MillerRabinVerdict::PrimeDeterministic
+        );
+        // strong pseudoprimes to the bases 2, 3 and 2, 3, 5
+        assert_eq!(
+            miller_rabin(&BigInt::from(1373653u64), 5, &mut rng),
+            MillerRabinVerdict::Composite
+        );
+        assert_eq!(
+            miller_rabin(&BigInt::from(25326001u64), 5, &mut rng),
+            MillerRabinVerdict::Composite
+        );
+        // 2⁶¹ - 1 is a Mersenne prime
+        let m61 = BigInt::from(2305843009213693951u64);
+        assert_eq!(
+            miller_rabin(&m61, 0, &mut rng),
+            MillerRabinVerdict::PrimeDeterministic
+        );
+        assert_eq!(
+            miller_rabin_deterministic_bases(&BigInt::from(2047u64))
+                .unwrap()
+                .len(),
+            2
+        );
+        let n = "3317044064679887385961981".parse::<BigInt>().unwrap();
+        assert_eq!(miller_rabin_deterministic_bases(&n), None);
+        // 2¹²⁷ - 1 is above the bounds
+        let m127 = BigInt::from(2u64).pow(127u32) - 1;
+        assert_eq!(
+            miller_rabin(&m127, 3, &mut rng),
+            MillerRabinVerdict::ProbablyPrime(3)
+        );
     }
 
     #[test]
-    fn finds_small_primes() {
-        let primes = vec![
-            BigInt::from(5u64),
-            BigInt::from(7u64),
-            BigInt::from(11u64),
-            BigInt::from(13u64),
-            BigInt::from(17u64),
-            BigInt::from(19u64),
-            BigInt::from(23u64),
-            BigInt::from(29u64),
-        ];
+    fn test_fermat_test() {
+        let bases = vec![BigInt::from(2u64), BigInt::from(3u64), BigInt::from(5u64)];
+        let (is_prime, table_data) = fermat_test(&BigInt::from(409u64), &bases);
+        assert_eq!(is_prime, Some(true));
+        assert_eq!(table_data.len(), 3);
+        // a ≡ 0 (mod n) is no witness, and with no other base there is no verdict
+        let bases = vec![BigInt::from(409u64), BigInt::from(2u64)];
+        assert_eq!(fermat_test(&BigInt::from(409u64), &bases).0, Some(true));
+        let bases = vec![BigInt::from(9u64), BigInt::from(18u64)];
+        let (is_prime, table_data) = fermat_test(&BigInt::from(9u64), &bases);
+        assert_eq!((is_prime, table_data.len()), (None, 2));
+        let (_, mut rng) = seeded_rng(Some(511));
+        let bases = random_bases(&mut rng, &BigInt::from(511u64), 5);
+        let (is_prime, _) = fermat_test(&BigInt::from(511u64), &bases);
+        assert_eq!(is_prime, Some(false));
+    }
+
+    #[test]
+    fn test_fermat_test_carmichael() {
+        // 561 = 3 x 11 x 17 fools the test for every coprime base
+        let bases = vec![BigInt::from(2u64), BigInt::from(5u64), BigInt::from(7u64)];
+        let (is_prime, _) = fermat_test(&BigInt::from(561u64), &bases);
+        assert_eq!(is_prime, Some(true));
+        let (is_prime, _) = miller_rabin_test(&BigInt::from(561u64), &BigInt::from(2u64));
+        assert!(!is_prime);
+    }
+
+    #[test]
+    fn test_miller_rabin_test_small_and_even() {
+        let two = BigInt::from(2u64);
+        for (n, expected) in [
+            (0u64, false),
+            (1, false),
+            (2, true),
+            (4, false),
+            (10, false),
+        ] {
+            let (is_prime, table_data) = miller_rabin_test(&BigInt::from(n), &two);
+            assert_eq!(is_prime, expected, "n = {}", n);
+            assert!(table_data.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_solovay_strassen() {
+        let (_, mut rng) = seeded_rng(Some(409));
+        let bases = random_bases(&mut rng, &BigInt::from(409u64), 5);
+        let (is_prime, _) = solovay_strassen(&BigInt::from(409u64), &bases);
+        assert_eq!(is_prime, Some(true));
+        // a ≡ 0 (mod n) is no witness, and with no other base there is no verdict
+        let bases = vec![BigInt::zero(), BigInt::from(2u64)];
         assert_eq!(
-            primes,
-            primes
-                .iter()
-                .map(|x| next_prime(&(x - BigInt::one())))
-                .collect::<Vec<BigInt>>()
+            solovay_strassen(&BigInt::from(409u64), &bases).0,
+            Some(true)
         );
+        let bases = vec![BigInt::from(9u64)];
+        assert_eq!(solovay_strassen(&BigInt::from(9u64), &bases).0, None);
+        // 561 is a Carmichael number but 5 is an Euler witness for it
+        let bases = vec![BigInt::from(5u64)];
+        let (is_prime, table_data) = solovay_strassen(&BigInt::from(561u64), &bases);
+        assert_eq!(is_prime, Some(false));
+        assert_eq!(table_data.len(), 1);
+        // 1729 is an Euler-Jacobi pseudoprime to the base 2
+        let bases = vec![BigInt::from(2u64)];
+        let (is_prime, _) = solovay_strassen(&BigInt::from(1729u64), &bases);
+        assert_eq!(is_prime, Some(true));
     }
 
     #[test]
-    fn returns_argument_when_it_is_already_prime() {
-        assert_eq!(next_prime(&BigInt::from(101)), BigInt::from(101));
+    fn test_baillie_psw() {
+        let primes = [2u64, 3, 5, 37, 41, 409, 2801, 3089, 1_000_000_007];
+        for p in primes {
+            assert!(baillie_psw(&BigInt::from(p)), "{} is prime", p);
+        }
+        // Carmichael numbers, strong pseudoprimes to the base 2 and a perfect square
+        let composites = [
+            1u64, 561, 1729, 2047, 3277, 4033, 4681, 8321, 1_194_649, 25326001,
+        ];
+        for n in composites {
+            assert!(!baillie_psw(&BigInt::from(n)), "{} is composite", n);
+        }
+        let n = "49675218696612399034240799519655205503986657506787162015105425670413948962864456158664793804627084299081036134562339483478437262146378569515417671690110863951848724044479367633926630234074394356492223".parse::<BigInt>().unwrap();
+        assert!(baillie_psw(&n));
+    }
+
+    #[test]
+    fn test_strong_lucas_probable_prime() {
+        // the smallest strong Lucas pseudoprimes are not base 2 strong pseudoprimes
+        for n in [5459u64, 5777, 10877, 16109, 18971] {
+            let n = BigInt::from(n);
+            assert!(strong_lucas_probable_prime(&n));
+            assert!(!strong_probable_prime(&n, &BigInt::from(2u64)));
+        }
+        assert!(strong_lucas_probable_prime(&BigInt::from(3089u64)));
+    }
+
+    #[test]
+    fn test_lucas_lehmer() {
+        let mersenne_exponents = [2u64, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127, 521];
+        for p in 2..130 {
+            assert_eq!(
+                lucas_lehmer(p),
+                mersenne_exponents.contains(&p),
+                "p = {}",
+                p
+            );
+        }
+        assert!(lucas_lehmer(521));
+    }
+
+    #[test]
+    fn test_mersenne_small_factor() {
+        // M₁₁ = 2047 = 23 x 89, M₂₉ = 233 x 1103 x 2089
+        assert_eq!(mersenne_small_factor(11, 10), Some(BigInt::from(23u64)));
+        assert_eq!(mersenne_small_factor(29, 10), Some(BigInt::from(233u64)));
+        assert_eq!(mersenne_small_factor(31, 1000), None);
+    }
+
+    #[test]
+    fn test_pepin() {
+        for m in 0..5 {
+            assert_eq!(pepin(m), Ok(true), "F{} is prime", m);
+        }
+        for m in 5..12 {
+            assert_eq!(pepin(m), Ok(false), "F{} is composite", m);
+        }
+        assert!(pepin(PEPIN_MAX_M + 1).is_err());
+        assert!(pepin(64).is_err());
+    }
+
+    #[test]
+    fn test_proth() {
+        // 13 = 3.2² + 1, 97 = 3.2⁵ + 1, 641 = 5.2⁷ + 1
+        assert_eq!(
+            proth(&BigInt::from(3u64), 2).unwrap(),
+            (true, Some(BigInt::from(2u64)))
+        );
+        assert!(proth(&BigInt::from(3u64), 5).unwrap().0);
+        assert!(proth(&BigInt::from(5u64), 7).unwrap().0);
+        // 49 = 3.2⁴ + 1 is a perfect square, 11.2⁴ + 1 = 177 = 3 x 59
+        assert_eq!(proth(&BigInt::from(3u64), 4).unwrap(), (false, None));
+        assert!(!proth(&BigInt::from(11u64), 4).unwrap().0);
+        assert!(proth(&BigInt::from(4u64), 4).is_err());
+        assert!(proth(&BigInt::from(17u64), 4).is_err());
+        assert!(proth(&BigInt::from(3u64), PROTH_MAX_N + 1).is_err());
+        assert!(proth(&BigInt::from(3u64), u64::MAX).is_err());
     }
 }
@@ -1,16 +1,17 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::primality::miller_rabin_primality;
+use crate::primality::baillie_psw;
 use dpc_pariter::IteratorExt;
 use num_bigint::BigInt;
 use num_iter::range_inclusive;
 use num_traits::identities::One;
-use num_traits::Zero;
+use num_traits::{ToPrimitive, Zero};
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
 
 pub trait PrimeFactors {
     fn prime_factors(&self, primes: &mut Vec<BigInt>) -> Vec<(BigInt, usize)>;
+    fn partial_prime_factors(&self, bound: u64) -> (Vec<(BigInt, usize)>, BigInt);
     //fn is_prime_factors_form_pq(&self) -> (bool, Vec<(BigInt, usize)>);
 }
 
@@ -18,7 +19,7 @@ impl PrimeFactors for BigInt {
     fn prime_factors(&self, primes: &mut Vec<BigInt>) -> Vec<(Self, usize)> {
         let n = self.clone();
         // Check if n is prime
-        if miller_rabin_primality(&self) {
+        if baillie_psw(self) {
             return vec![(self.clone(), 1)];
         }
 
@@ -35,7 +36,7 @@ impl PrimeFactors for BigInt {
             let new_primes: Vec<BigInt> = r
                 .into_iter()
                 .map(|x| x)
-                .parallel_filter(|x| miller_rabin_primality(x))
+                .parallel_filter(baillie_psw)
                 .collect();
             primes.extend(new_primes);
             let mut seen = HashSet::new();
@@ -62,7 +63,7 @@ impl PrimeFactors for BigInt {
                     .fold(BigInt::one(), |acc: BigInt, a| acc * a);
             let q = &n / &product;
             // if q is a prime, q is the last factor, else we need to factor q.
-            if miller_rabin_primality(&q) {
+            if baillie_psw(&q) {
                 all_divisors.push(q);
                 break;
             }
@@ -80,6 +81,43 @@ impl PrimeFactors for BigInt {
         res.sort_by_key(|k| k.0.clone());
         res
     }
+
+    ///
+    /// Trial division by the integers 2 <= d <= bound. Returns the prime factors found
+    /// and the unfactored cofactor, which has no prime factor <= bound. The cofactor is
+    /// 1 when the factorisation is complete.
+    ///
+    fn partial_prime_factors(&self, bound: u64) -> (Vec<(BigInt, usize)>, BigInt) {
+        let mut factors = Vec::<(BigInt, usize)>::new();
+        let mut rest = self.clone();
+        let limit = |rest: &BigInt| {
+            std::cmp::min(BigInt::from(bound), rest.sqrt())
+                .to_u64()
+                .unwrap()
+        };
+        let mut max_d = limit(&rest);
+        let mut d = 2u64;
+
+        while d <= max_d {
+            let mut exp = 0;
+            while (&rest % d).is_zero() {
+                rest /= d;
+                exp += 1;
+            }
+            if exp > 0 {
+                factors.push((BigInt::from(d), exp));
+                max_d = limit(&rest);
+            }
+            d += if d == 2 { 1 } else { 2 };
+        }
+
+        // whatever is left is prime when it has no factor <= sqrt(rest)
+        if rest > BigInt::one() && BigInt::from(d) * d > rest {
+            factors.push((rest, 1));
+            rest = BigInt::one();
+        }
+        (factors, rest)
+    }
 }
 
 #[cfg(test)]
@@ -96,4 +134,24 @@ mod tests {
             vec![(BigInt::from(2u64), 2), (BigInt::from(5u64), 2)]
         );
     }
+
+    #[test]
+    fn test_partial_prime_factors() {
+        let (factors, rest) = BigInt::from(360u64).partial_prime_factors(100);
+        assert_eq!(
+            factors,
+            vec![
+                (BigInt::from(2u64), 3),
+                (BigInt::from(3u64), 2),
+                (BigInt::from(5u64), 1)
+            ]
+        );
+        assert_eq!(rest, BigInt::one());
+
+        // 2⁴ x 1000003 x 1000033, both cofactor primes beyond the bound
+        let n = BigInt::from(16u64) * BigInt::from(1000003u64) * BigInt::from(1000033u64);
+        let (factors, rest) = n.partial_prime_factors(1000);
+        assert_eq!(factors, vec![(BigInt::from(2u64), 4)]);
+        assert_eq!(rest, BigInt::from(1000003u64) * BigInt::from(1000033u64));
+    }
 }
@@ -0,0 +1,340 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive};
+use rand::{rngs::StdRng, Rng};
+use rayon::prelude::*;
+
+use crate::{
+    certificates::{certify, Certificate},
+    primality::{baillie_psw, strong_probable_prime},
+    sieve::small_primes,
+    utils::{generate_random_int_in_range, modular_pow},
+};
+
+/// Candidates are sieved by the primes below this bound before any probable prime test.
+const SIEVE_BOUND: u64 = 1 << 16;
+/// Number of candidates sieved at a time.
+const WINDOW: usize = 4096;
+/// Bits of the multiplier k in the provable primes p = 2kq + 1.
+const K_BITS: u64 = 16;
+/// Provable primes up to this size are certified directly.
+const PROVABLE_BASE_BITS: u64 = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrimeKind {
+    /// any odd prime
+    Any,
+    /// p with (p - 1)/2 prime
+    Safe,
+    /// p - 1 and p + 1 each have a large prime factor r and s, and r - 1 has a large
+    /// prime factor t (Gordon's algorithm)
+    Strong,
+    /// p with 2p + 1 prime
+    SophieGermain,
+}
+
+#[derive(Clone, Debug)]
+pub struct GeneratedPrime {
+    pub p: BigInt,
+    /// the primes that give p its structure, e.g. q = (p - 1)/2 for a safe prime
+    pub structure: Vec<(String, BigInt)>,
+}
+
+///
+/// Indices i < WINDOW for which start + i.step has no prime factor in `primes`, and
+/// neither has 2(start + i.step) + 1 when `twin` is set.
+///
+fn sieve_window(start: &BigInt, step: &BigInt, primes: &[u64], twin: bool) -> Vec<usize> {
+    let mut composite = vec![false; WINDOW];
+    let mut strike = |first: u64, p: u64| {
+        (first as usize..WINDOW)
+            .step_by(p as usize)
+            .for_each(|i| composite[i] = true)
+    };
+
+    for &p in primes {
+        let s = (start % p).to_u64().unwrap();
+        let st = (step % p).to_u64().unwrap();
+        if st == 0 {
+            // every candidate is ≡ start (mod p)
+            if s == 0 || (twin && (2 * s + 1).is_multiple_of(p)) {
+                return Vec::new();
+            }
+            continue;
+        }
+        let inv = modular_pow(&BigInt::from(st), &BigInt::from(p - 2), &BigInt::from(p))
+            .to_u64()
+            .unwrap();
+        // start + i.step ≡ 0 (mod p)
+        strike((p - s) % p * inv % p, p);
+        // 2(start + i.step) + 1 ≡ 0 (mod p), i.e. start + i.step ≡ (p - 1)/2 (mod p)
+        if twin && p != 2 {
+            strike(((p - 1) / 2 + p - s) % p * inv % p, p);
+        }
+    }
+    (0..WINDOW).filter(|i| !composite[*i]).collect()
+}
+
+///
+/// First x = start + i.step < limit, scanning a window at a time, that survives the
+/// sieve by `primes`, the primes below SIEVE_BOUND, and passes `test`. Returns None when
+/// the search runs past the limit.
+///
+fn sieve_search(
+    primes: &[u64],
+    start: &BigInt,
+    step: &BigInt,
+    limit: &BigInt,
+    twin: bool,
+    test: impl Fn(&BigInt) -> bool + Sync,
+) -> Option<BigInt> {
+    // a sieving prime must be smaller than the candidates it strikes out
+    let primes = &primes[..primes.partition_point(|p| BigInt::from(*p) < *start)];
+    let mut base = start.clone();
+
+    while &base < limit {
+        let found = sieve_window(&base, step, primes, twin)
+            .into_par_iter()
+            .map(|i| &base + step * i)
+            .find_first(|x| x < limit && test(x));
+        if found.is_some() {
+            return found;
+        }
+        base += step * WINDOW;
+    }
+    None
+}
+
+fn is_sophie_germain(x: &BigInt) -> bool {
+    let two = BigInt::from(2u64);
+    let p = 2u32 * x + 1u32;
+    strong_probable_prime(x, &two)
+        && strong_probable_prime(&p, &two)
+        && baillie_psw(x)
+        && baillie_psw(&p)
+}
+
+///
+/// Random odd x with exactly `bits` bits, x is searched upwards from there.
+///
+fn random_odd(rng: &mut StdRng, bits: u64) -> BigInt {
+    let low = BigInt::one() << (bits - 1);
+    generate_random_int_in_range(rng, &low, &(&low << 1)) | BigInt::one()
+}
+
+///
+/// Search upwards from random starting points for x with exactly `bits` bits passing
+/// the test.
+///
+fn random_search(
+    primes: &[u64],
+    rng: &mut StdRng,
+    bits: u64,
+    twin: bool,
+    test: impl Fn(&BigInt) -> bool + Sync + Copy,
+) -> BigInt {
+    let limit = BigInt::one() << bits;
+    loop {
+        let start = random_odd(rng, bits);
+        if let Some(x) = sieve_search(primes, &start, &BigInt::from(2u64), &limit, twin, test) {
+            return x;
+        }
+    }
+}
+
+///
+/// Gordon's algorithm: primes s and t, then the prime r = 2it + 1, and
+/// p = p₀ + 2jrs with p₀ = 2(sʳ⁻² mod r)s - 1, so that p ≡ 1 (mod r) and p ≡ -1 (mod s).
+///
+fn strong_prime(primes: &[u64], bits: u64, rng: &mut StdRng) -> GeneratedPrime {
+    let half = bits / 2;
+    let s = random_search(primes, rng, half - 16, false, baillie_psw);
+    let t = random_search(primes, rng, half - 24, false, baillie_psw);
+
+    let two_t = 2u32 * &t;
+    let i = rng.gen_range(1u64 << 7..1u64 << 8);
+    let r = sieve_search(
+        primes,
+        &(&two_t * i + 1u32),
+        &two_t,
+        &(BigInt::one() << bits),
+        false,
+        baillie_psw,
+    )
+    .expect("r < 2ᵇⁱᵗˢ");
+
+    let p_0 = 2u32 * modular_pow(&s, &(&r - 2u32), &r) * &s - 1u32;
+    let two_rs = 2u32 * &r * &s;
+    let (low, high) = (BigInt::one() << (bits - 1), BigInt::one() << bits);
+    let j_min = (&low - &p_0).div_ceil(&two_rs);
+    let j_max = (&high - &p_0) / &two_rs;
+    loop {
+        let j = generate_random_int_in_range(rng, &j_min, &j_max);
+        let start = &p_0 + &j * &two_rs;
+        if let Some(p) = sieve_search(primes, &start, &two_rs, &high, false, baillie_psw) {
+            return GeneratedPrime {
+                p,
+                structure: vec![
+                    ("r | p - 1".to_string(), r),
+                    ("s | p + 1".to_string(), s),
+                    ("t | r - 1".to_string(), t),
+                ],
+            };
+        }
+    }
+}
+
+fn check_bits(bits: u64, kind: PrimeKind) -> Result<(), String> {
+    let min_bits = match kind {
+        PrimeKind::Any | PrimeKind::SophieGermain => 2,
+        PrimeKind::Safe => 3,
+        PrimeKind::Strong => 64,
+    };
+    if bits < min_bits {
+        return Err(format!(
+            "{:?} primes need at least {} bits, got {}\n",
+            kind, min_bits, bits
+        ));
+    }
+    Ok(())
+}
+
+fn with_structure(p: BigInt, kind: PrimeKind) -> GeneratedPrime {
+    let structure = match kind {
+        PrimeKind::Safe => vec![("q = (p - 1)/2".to_string(), (&p - 1u32) / 2u32)],
+        PrimeKind::SophieGermain => vec![("2p + 1".to_string(), 2u32 * &p + 1u32)],
+        _ => Vec::new(),
+    };
+    GeneratedPrime { p, structure }
+}
+
+///
+/// Random odd prime with exactly `bits` bits. Candidates are sieved by the small primes
+/// and the survivors tested with a strong probable prime test to base 2 followed by
+/// Baillie-PSW, for both p and 2p + 1 in the safe and Sophie Germain cases.
+///
+pub fn random_prime(
+    bits: u64,
+    kind: PrimeKind,
+    rng: &mut StdRng,
+) -> Result<GeneratedPrime, String> {
+    check_bits(bits, kind)?;
+    let primes = small_primes(SIEVE_BOUND);
+    let p = match kind {
+        PrimeKind::Any => random_search(&primes, rng, bits, false, baillie_psw),
+        PrimeKind::SophieGermain => random_search(&primes, rng, bits, true, is_sophie_germain),
+        PrimeKind::Safe => {
+            2u32 * random_search(&primes, rng, bits - 1, true, is_sophie_germain) + 1u32
+        }
+        PrimeKind::Strong => return Ok(strong_prime(&primes, bits, rng)),
+    };
+    Ok(with_structure(p, kind))
+}
+
+///
+/// Prime x with `bits` bits of the form x = 2kq + 1, k < 2¹⁶ and q itself such a prime,
+/// down to 32 bits. n - 1 then factors completely by trial division up to the large
+/// prime q, which is what a Pocklington certificate needs at every level.
+///
+fn provable_search(primes: &[u64], rng: &mut StdRng, bits: u64, twin: bool) -> BigInt {
+    let test: fn(&BigInt) -> bool = if twin { is_sophie_germain } else { baillie_psw };
+    if bits <= PROVABLE_BASE_BITS {
+        return random_search(primes, rng, bits, twin, test);
+    }
+
+    let (low, high) = (BigInt::one() << (bits - 1), BigInt::one() << bits);
+    loop {
+        let q = provable_search(primes, rng, bits - K_BITS, false);
+        let two_q = 2u32 * &q;
+        let k_min = (&low - 1u32).div_ceil(&two_q);
+        let k_max = (&high - 2u32) / &two_q;
+        let k = generate_random_int_in_range(rng, &k_min, &k_max);
+        // try the rest of the k range before drawing a new q
+        let start = &two_q * &k + 1u32;
+        if let Some(x) = sieve_search(primes, &start, &two_q, &high, twin, test) {
+            return x;
+        }
+    }
+}
+
+///
+/// Random prime with exactly `bits` bits together with a Pocklington certificate.
+/// The primes are constructed as p = 2kq + 1 with q a smaller provable prime (Maurer's
+/// method), so they are not uniformly distributed among the primes of that size.
+///
+pub fn random_provable_prime(
+    bits: u64,
+    kind: PrimeKind,
+    rng: &mut StdRng,
+) -> Result<(GeneratedPrime, Certificate), String> {
+    check_bits(bits, kind)?;
+    let primes = small_primes(SIEVE_BOUND);
+    let p = match kind {
+        PrimeKind::Any => provable_search(&primes, rng, bits, false),
+        PrimeKind::SophieGermain => provable_search(&primes, rng, bits, true),
+        PrimeKind::Safe => 2u32 * provable_search(&primes, rng, bits - 1, true) + 1u32,
+        PrimeKind::Strong => {
+            return Err("certificates are not generated for strong primes\n".to_string())
+        }
+    };
+    let certificate = certify(&p, true)?;
+    Ok((with_structure(p, kind), certificate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{certificates::verify_certificate, utils::seeded_rng};
+
+    #[test]
+    fn test_random_prime() {
+        let (_, mut rng) = seeded_rng(Some(7));
+        for bits in [2, 3, 10, 64, 128] {
+            let prime = random_prime(bits, PrimeKind::Any, &mut rng).unwrap();
+            assert_eq!(prime.p.bits(), bits);
+            assert!(baillie_psw(&prime.p));
+        }
+
+        let prime = random_prime(64, PrimeKind::Safe, &mut rng).unwrap();
+        assert_eq!(prime.p.bits(), 64);
+        assert!(baillie_psw(&prime.p) && baillie_psw(&prime.structure[0].1));
+
+        let prime = random_prime(64, PrimeKind::SophieGermain, &mut rng).unwrap();
+        assert_eq!(prime.p.bits(), 64);
+        assert!(baillie_psw(&prime.p) && baillie_psw(&prime.structure[0].1));
+
+        let prime = random_prime(128, PrimeKind::Strong, &mut rng).unwrap();
+        assert_eq!(prime.p.bits(), 128);
+        let (r, s, t) = (
+            &prime.structure[0].1,
+            &prime.structure[1].1,
+            &prime.structure[2].1,
+        );
+        assert!((&prime.p - 1u32).is_multiple_of(r));
+        assert!((&prime.p + 1u32).is_multiple_of(s));
+        assert!((r - 1u32).is_multiple_of(t));
+
+        assert!(random_prime(2, PrimeKind::Safe, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_random_prime_is_reproducible() {
+        let (_, mut rng1) = seeded_rng(Some(42));
+        let (_, mut rng2) = seeded_rng(Some(42));
+        assert_eq!(
+            random_prime(256, PrimeKind::Any, &mut rng1).unwrap().p,
+            random_prime(256, PrimeKind::Any, &mut rng2).unwrap().p
+        );
+    }
+
+    #[test]
+    fn test_random_provable_prime() {
+        let (_, mut rng) = seeded_rng(Some(3));
+        for kind in [PrimeKind::Any, PrimeKind::Safe, PrimeKind::SophieGermain] {
+            let (prime, certificate) = random_provable_prime(96, kind, &mut rng).unwrap();
+            assert_eq!(prime.p.bits(), 96);
+            assert_eq!(verify_certificate(&certificate).unwrap(), prime.p);
+        }
+        assert!(random_provable_prime(128, PrimeKind::Strong, &mut rng).is_err());
+    }
+}
@@ -1,92 +1,325 @@
-use std::collections::{HashMap, HashSet};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
 
-use num_bigint::{BigInt, Sign};
-use num_iter::range_inclusive;
-use num_traits::One;
+use crate::{
+    display::{QsDependencyTable, QsFactorBaseTable, QsRelationTable},
+    gf2::SparseMatrixGf2,
+    sieve::segmented_sieve,
+    utils::{jacobi_symbol, sqrt_mod_prime, Gcd},
+};
 
-use crate::{prime_factors::PrimeFactors, utils::modular_pow};
+/// Smallest smoothness bound B, below it the factor base has hardly any primes.
+const QS_MIN_BOUND: u64 = 30;
+/// Values of t sieved at a time.
+const QS_BLOCK: u64 = 1 << 15;
+/// Blocks in a row without a relation before the search gives up, y(t) grows with |t - √n|.
+const QS_IDLE_BLOCKS: u64 = 64;
+/// Relations collected beyond the size of the factor base, each one more dependency.
+const QS_EXTRA_RELATIONS: usize = 10;
 
-fn zeros(size: usize) -> Vec<i32> {
-    let mut zero_vec: Vec<i32> = Vec::with_capacity(size);
-    for _ in 0..size {
-        zero_vec.push(0);
-    }
-    return zero_vec;
+///
+/// A run of the quadratic sieve: the bound B, the number of t sieved, the factor base
+/// with the square roots of n, the relations, the dependencies tried in the square
+/// root step and the factor they gave.
+///
+#[derive(Clone, Debug)]
+pub struct QuadraticSieveTrace {
+    pub bound: u64,
+    pub sieved: u64,
+    pub factor_base: Vec<QsFactorBaseTable>,
+    pub relations: Vec<QsRelationTable>,
+    pub dependencies: Vec<QsDependencyTable>,
+    pub factor: Option<BigInt>,
+}
+
+///
+/// A relation t² ≡ y (mod n), y = t² - n, with y = ±p₁^e₁ ... pₖ^eₖ over the factor base.
+/// exponents[0] is that of -1.
+///
+#[derive(Clone, Debug)]
+struct Relation {
+    t: BigInt,
+    y: BigInt,
+    exponents: Vec<u32>,
 }
 
-pub fn prepare_matrix(n: &BigInt) {
-    let mut primes = vec![BigInt::from(2u64)];
-    let a = n.sqrt();
-    println!("Square Root of {} = {}", n, a);
-
-    let mut factor_base = vec![
-        BigInt::from(2u64),
-        BigInt::from(5u64),
-        BigInt::from(7u64),
-        BigInt::from(11u64),
-        BigInt::from(13u64),
-        BigInt::from(17u64),
-        BigInt::from(19u64),
-        BigInt::from(23u64),
-        BigInt::from(29u64),
-        BigInt::from(31u64),
-        BigInt::from(37u64),
-        BigInt::from(41u64),
-    ];
-
-    println!("Legendre Symbol is calculated using Euler's criteria: ");
-    println!("If n^(p-1)/2 (mod p) = 1, then (n/p) = 1, else (n/p) = -1");
-    factor_base.retain(|x| modular_pow(n, &((x - 1) / BigInt::from(2u64)), x) == BigInt::one());
-    //factor_base.insert(0, BigInt::from(-1i32));
-    println!("The calculated Factor Base is: {:?}", &factor_base);
-    let mut y_x: Vec<BigInt> = Vec::new();
-    // start = sqrt(n) - 100, end = sqrt(n) + 200
-    // These values should be dynamic
-    let start = a.clone() - BigInt::from(100u64);
-    let end = a.clone() + BigInt::from(200u64);
-
-    let mut m_by_n: Vec<Vec<i32>> = Vec::new();
-    for i in range_inclusive(start, end) {
-        let x = &i - &a;
-        y_x.push(x.clone());
-        // y(x) = (x + a)^2 - n
-        let mut y = &i * &i - n;
-        if y.sign() == Sign::Minus {
-            y = -1 * y;
+///
+/// B = L(n)¹ᐟ² with L(n) = exp(√(ln n ln ln n)), which balances the chance that y(t)
+/// is B-smooth against the number of relations needed
+///
+pub fn qs_bound(n: &BigInt) -> u64 {
+    let ln_n = n.bits() as f64 * std::f64::consts::LN_2;
+    let l_n = (ln_n * ln_n.ln()).sqrt().exp();
+    std::cmp::max(l_n.sqrt() as u64, QS_MIN_BOUND)
+}
+
+///
+/// The primes p <= B with (n/p) = 1, and 2, with the roots ±t of t² ≡ n (mod p).
+/// Err(p) when a prime p <= B divides n.
+///
+fn factor_base(n: &BigInt, bound: u64) -> Result<Vec<(u64, Vec<u64>)>, u64> {
+    let mut base = Vec::new();
+    for p in segmented_sieve(2, bound) {
+        let p_big = BigInt::from(p);
+        if (n % &p_big).is_zero() {
+            return Err(p);
         }
-        let p_factors = y.prime_factors(&mut primes).clone();
-        let p_factors_map: HashMap<BigInt, i32> = p_factors
-            .iter()
-            .cloned()
-            .map(|(p, e)| (p, e as i32))
-            .collect();
-        let distinct_factors = p_factors
-            .iter()
-            .map(|x| x.0.clone())
-            .collect::<Vec<BigInt>>();
-        let set1: HashSet<BigInt> = factor_base.iter().cloned().collect();
-        let set2: HashSet<BigInt> = distinct_factors.iter().cloned().collect();
-
-        if set2.is_subset(&set1) {
-            // println!("{} {} {:?}", i - &a, &y, p_factors);
-
-            let mut one_by_n: Vec<i32> = Vec::new();
-            for base in factor_base.iter() {
-                if set2.contains(&base) {
-                    let e = p_factors_map.get(&base).unwrap();
-                    one_by_n.push(e.clone());
-                } else {
-                    one_by_n.push(0);
-                }
+        if p == 2 {
+            base.push((2, vec![1]));
+            continue;
+        }
+        if jacobi_symbol(n, &p_big) != 1 {
+            continue;
+        }
+        let t = sqrt_mod_prime(&(n % &p_big), &p_big)
+            .and_then(|t| t.to_u64())
+            .expect("n is a quadratic residue mod p");
+        base.push((p, vec![t, p - t]));
+    }
+    Ok(base)
+}
+
+///
+/// Sieve the t in [lo, hi) for y(t) = t² - n smooth over the factor base. log₂p is added
+/// at every t ≡ ±√n (mod p), a t whose sum comes within 2.log₂B of log₂|y(t)| is
+/// trial divided by the primes that hit it.
+///
+fn sieve_block(
+    n: &BigInt,
+    base: &[(u64, Vec<u64>)],
+    lo: &BigInt,
+    hi: &BigInt,
+    relations: &mut Vec<Relation>,
+    needed: usize,
+) {
+    let len = (hi - lo).to_usize().expect("block length");
+    let mut logs = vec![0u8; len];
+    for (p, roots) in base {
+        let log_p = (*p as f64).log2().round() as u8;
+        let lo_mod_p = (lo % p).to_u64().expect("residue");
+        for r in roots {
+            let mut i = ((r + p - lo_mod_p) % p) as usize;
+            while i < len {
+                logs[i] = logs[i].saturating_add(log_p);
+                i += *p as usize;
             }
+        }
+    }
+
+    let slack = 2.0 * (base[base.len() - 1].0 as f64).log2();
+    let sqrt_n = n.sqrt();
+    let c = (&sqrt_n * &sqrt_n - n).to_f64().expect("a² - n");
+    let a = sqrt_n.to_f64().expect("√n");
+    let lo_x = (lo - &sqrt_n).to_f64().expect("x");
+    for (i, log) in logs.iter().enumerate() {
+        // |y(t)| = |2ax + x² + a² - n| with t = a + x, a = ⌊√n⌋
+        let x = lo_x + i as f64;
+        let log_y = (2.0 * a * x + x * x + c).abs().max(1.0).log2();
+        if (*log as f64) < log_y - slack {
+            continue;
+        }
 
-            if x.sign() == Sign::Minus {
-                one_by_n.insert(0, 1);
-            } else {
-                one_by_n.insert(0, 0);
+        let t = lo + i;
+        let y = &t * &t - n;
+        let mut rest = y.abs();
+        let mut exponents = vec![0u32; base.len() + 1];
+        exponents[0] = u32::from(y.is_negative());
+        for (k, (p, roots)) in base.iter().enumerate() {
+            let t_mod_p = (&t % p).to_u64().expect("residue");
+            if !roots.contains(&t_mod_p) {
+                continue;
             }
-            m_by_n.push(one_by_n.clone());
-            println!("{:>5} {:>5}  {:?}", x, i, one_by_n);
+            let (mut q, mut r) = rest.div_rem(&BigInt::from(*p));
+            while r.is_zero() {
+                rest = q;
+                exponents[k + 1] += 1;
+                (q, r) = rest.div_rem(&BigInt::from(*p));
+            }
+        }
+        if rest.is_one() && !y.is_zero() {
+            relations.push(Relation { t, y, exponents });
+            if relations.len() >= needed {
+                return;
+            }
+        }
+    }
+}
+
+///
+/// The quadratic sieve. Returns a nontrivial factor of the odd composite n, not a prime
+/// power, along with the factor base, the relations and the dependencies tried.
+///
+/// 1. B = L(n)¹ᐟ², at least 30, the factor base is -1, 2 and the odd primes p <= B with
+///    (n/p) = 1
+/// 2. t runs outwards from √n in blocks, y(t) = t² - n is log-sieved with the roots
+///    ±√n (mod p) from Tonelli-Shanks until there are |factor base| + 10 relations
+/// 3. Gaussian elimination over GF(2) on the exponent vectors mod 2 gives subsets S
+///    with ∏ y(t) a square
+/// 4. X = ∏ t, Y = √∏ y(t) (mod n), X² ≡ Y² (mod n), and gcd(X - Y, n) is a factor
+///    unless X ≡ ±Y
+///
+pub fn quadratic_sieve(n: &BigInt, bound: Option<u64>) -> QuadraticSieveTrace {
+    // a smaller B leaves the factor base empty or without the relations to fill it
+    let bound = bound.unwrap_or_else(|| qs_bound(n)).max(QS_MIN_BOUND);
+    let mut trace = QuadraticSieveTrace {
+        bound,
+        sieved: 0,
+        factor_base: Vec::new(),
+        relations: Vec::new(),
+        dependencies: Vec::new(),
+        factor: None,
+    };
+    let sqrt_n = n.sqrt();
+    if &(&sqrt_n * &sqrt_n) == n {
+        trace.factor = Some(sqrt_n);
+        return trace;
+    }
+
+    let base = match factor_base(n, bound) {
+        Ok(base) => base,
+        Err(p) => {
+            trace.factor = Some(BigInt::from(p));
+            return trace;
         }
+    };
+    trace.factor_base = base
+        .iter()
+        .map(|(p, roots)| {
+            QsFactorBaseTable::new(
+                p.to_string(),
+                format!("{:?}", roots),
+                format!("{:.2}", (*p as f64).log2()),
+            )
+        })
+        .collect();
+
+    // blocks alternate above and below √n until t reaches 1
+    let needed = base.len() + 1 + QS_EXTRA_RELATIONS;
+    let mut relations: Vec<Relation> = Vec::new();
+    let (mut above, mut below) = (&sqrt_n + 1u32, sqrt_n.clone() + 1u32);
+    let (mut block, mut idle) = (0u64, 0u64);
+    while relations.len() < needed && idle < QS_IDLE_BLOCKS {
+        let found = relations.len();
+        let (lo, hi) = if block % 2 == 0 || below <= BigInt::one() {
+            let hi = &above + QS_BLOCK;
+            (std::mem::replace(&mut above, hi.clone()), hi)
+        } else {
+            let lo = std::cmp::max(&below - QS_BLOCK, BigInt::one());
+            (lo.clone(), std::mem::replace(&mut below, lo))
+        };
+        sieve_block(n, &base, &lo, &hi, &mut relations, needed);
+        trace.sieved += (hi - lo).to_u64().expect("block length");
+        idle = if relations.len() > found { 0 } else { idle + 1 };
+        block += 1;
+    }
+    relations.sort_by(|r, s| r.t.cmp(&s.t));
+
+    let labels = std::iter::once("-1".to_string())
+        .chain(base.iter().map(|(p, _)| p.to_string()))
+        .collect::<Vec<String>>();
+    trace.relations = relations
+        .iter()
+        .map(|r| {
+            let factors = r
+                .exponents
+                .iter()
+                .zip(&labels)
+                .filter(|(e, _)| **e > 0)
+                .map(|(e, p)| match e {
+                    1 => p.clone(),
+                    _ => format!("{}^{}", p, e),
+                })
+                .collect::<Vec<String>>()
+                .join(" x ");
+            let vector = r
+                .exponents
+                .iter()
+                .map(|e| (e % 2).to_string())
+                .collect::<String>();
+            QsRelationTable::new(
+                r.t.to_string(),
+                (&r.t - &sqrt_n).to_string(),
+                r.y.to_string(),
+                factors,
+                vector,
+            )
+        })
+        .collect();
+
+    let mut matrix = SparseMatrixGf2::new(labels.len());
+    for r in &relations {
+        matrix
+            .push_row((0..r.exponents.len() as u32).filter(|&k| r.exponents[k as usize] % 2 == 1));
+    }
+
+    for (d, dependency) in matrix.nullspace().dependencies.iter().enumerate() {
+        let x = dependency
+            .iter()
+            .fold(BigInt::one(), |acc, &i| acc * &relations[i].t % n);
+        let mut exponents = vec![0u32; labels.len()];
+        for &i in dependency {
+            exponents
+                .iter_mut()
+                .zip(&relations[i].exponents)
+                .for_each(|(e, f)| *e += f);
+        }
+        let y = base
+            .iter()
+            .zip(&exponents[1..])
+            .fold(BigInt::one(), |acc, ((p, _), e)| {
+                acc * BigInt::from(*p).modpow(&BigInt::from(e / 2), n) % n
+            });
+        let g = (&x - &y).mod_floor(n).gcd_euclid(n);
+        trace.dependencies.push(QsDependencyTable::new(
+            (d + 1).to_string(),
+            dependency.len().to_string(),
+            x.to_string(),
+            y.to_string(),
+            g.to_string(),
+        ));
+        if !g.is_one() && &g != n {
+            trace.factor = Some(g);
+            break;
+        }
+    }
+    trace
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quadratic_sieve() {
+        // 87463 = 149 x 587
+        let trace = quadratic_sieve(&BigInt::from(87463u64), None);
+        let primes = trace
+            .factor_base
+            .iter()
+            .map(|row| row.p())
+            .collect::<Vec<&str>>();
+        assert_eq!(primes, vec!["2", "3", "13", "17", "19", "29"]);
+        let d = trace.factor.unwrap();
+        assert!(d == BigInt::from(149u64) || d == BigInt::from(587u64));
+        let trace = quadratic_sieve(&BigInt::from(87463u64), Some(1));
+        assert_eq!(trace.bound, QS_MIN_BOUND);
+        assert!(trace.factor.is_some());
+
+        // (10⁹ + 7)(10⁹ + 9) and a 25 digit n
+        let n = BigInt::from(1000000016000000063u64);
+        let d = quadratic_sieve(&n, None).factor.unwrap();
+        assert!(d == BigInt::from(1000000007u64) || d == BigInt::from(1000000009u64));
+        let (p, q) = (
+            BigInt::from(1000000000039u64),
+            BigInt::from(1000000000061u64),
+        );
+        let d = quadratic_sieve(&(&p * &q), None).factor.unwrap();
+        assert!(d == p || d == q);
+
+        assert_eq!(
+            quadratic_sieve(&BigInt::from(391u64), None).factor,
+            Some(BigInt::from(17u64))
+        );
     }
 }
@@ -0,0 +1,450 @@
+use num_bigint::BigInt;
+use num_integer::{Integer, Roots};
+use num_iter::range_inclusive;
+use num_traits::{ToPrimitive, Zero};
+use rayon::prelude::*;
+
+use crate::primality::baillie_psw;
+
+/// Ranges ending below this are sieved exactly, the sieving primes up to √END fit in memory.
+pub const SIEVE_LIMIT: u64 = 10_000_000_000_000_000;
+/// Largest x of the prime-count command, π(10¹⁴) takes under a minute.
+pub const PRIME_COUNT_LIMIT: u64 = 100_000_000_000_000;
+/// Largest n of nth_prime, π(10¹⁴), so that π is only ever computed below PRIME_COUNT_LIMIT.
+pub const NTH_PRIME_LIMIT: u64 = 3_204_941_750_802;
+/// Odd numbers per segment.
+const SEGMENT: usize = 1 << 18;
+/// Primes of the wheel, their odd multiples are struck out by copying a precomputed pattern.
+const WHEEL_PRIMES: [u64; 5] = [3, 5, 7, 11, 13];
+/// 3 x 5 x 7 x 11 x 13, the period of the wheel pattern over the odd numbers.
+const WHEEL_PERIOD: usize = 15015;
+
+///
+/// Sieve of Eratosthenes for the primes below the bound
+///
+pub fn small_primes(bound: u64) -> Vec<u64> {
+    let mut is_prime = vec![true; bound as usize];
+    let mut primes = Vec::new();
+    for i in 2..bound as usize {
+        if is_prime[i] {
+            primes.push(i as u64);
+            (i * i..bound as usize)
+                .step_by(i)
+                .for_each(|j| is_prime[j] = false);
+        }
+    }
+    primes
+}
+
+///
+/// pattern[j] is true when 2j + 1 is divisible by a wheel prime
+///
+fn wheel_pattern() -> Vec<bool> {
+    (0..WHEEL_PERIOD as u64)
+        .map(|j| WHEEL_PRIMES.iter().any(|p| (2 * j + 1) % p == 0))
+        .collect()
+}
+
+///
+/// Primes among the odd numbers lo, lo + 2, ..., lo + 2(len - 1), lo odd and >= 17.
+/// The wheel pattern takes care of 3, 5, 7, 11 and 13, `base` holds the sieving primes
+/// from 17 up to the square root of the segment end.
+///
+fn sieve_segment(lo: u64, len: usize, base: &[u64], pattern: &[bool]) -> Vec<u64> {
+    let hi = lo + 2 * (len as u64 - 1);
+    let j0 = ((lo - 1) / 2) as usize % WHEEL_PERIOD;
+    let mut composite = pattern
+        .iter()
+        .cycle()
+        .skip(j0)
+        .take(len)
+        .copied()
+        .collect::<Vec<bool>>();
+
+    for &p in base.iter().take_while(|p| *p * *p <= hi) {
+        // first odd multiple of p that is >= max(p², lo)
+        let mut m = std::cmp::max(p * p, lo.div_ceil(p) * p);
+        if m % 2 == 0 {
+            m += p;
+        }
+        ((m - lo) / 2..len as u64)
+            .step_by(p as usize)
+            .for_each(|i| composite[i as usize] = true);
+    }
+
+    (0..len)
+        .filter(|i| !composite[*i])
+        .map(|i| lo + 2 * i as u64)
+        .collect()
+}
+
+///
+/// Primes in [lo, hi] in increasing order, produced by a segmented sieve of
+/// Eratosthenes on the odd numbers with a 2.3.5.7.11.13 wheel. A batch of segments
+/// (one per thread) is sieved in parallel at a time, so memory stays O(√hi + batch)
+/// however wide the range is.
+///
+pub struct PrimeStream {
+    base: Vec<u64>,
+    pattern: Vec<bool>,
+    /// next odd number to sieve
+    lo: u64,
+    hi: u64,
+    buffer: std::vec::IntoIter<u64>,
+}
+
+impl PrimeStream {
+    pub fn new(lo: u64, hi: u64) -> Self {
+        let wheel = [2, 3, 5, 7, 11, 13]
+            .into_iter()
+            .filter(|p| lo <= *p && *p <= hi)
+            .collect::<Vec<u64>>();
+        let odd_lo = std::cmp::max(lo, 17) | 1;
+
+        // sieving primes 17 <= p <= √hi, themselves from a (much smaller) segmented sieve
+        let root = if odd_lo <= hi { hi.sqrt() } else { 0 };
+        let base = if root < 1 << 16 {
+            small_primes(root + 1)
+        } else {
+            segmented_sieve(2, root)
+        };
+
+        Self {
+            base: base.into_iter().filter(|p| *p >= 17).collect(),
+            pattern: wheel_pattern(),
+            lo: odd_lo,
+            hi,
+            buffer: wheel.into_iter(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let odd_count = ((self.hi - self.lo) / 2 + 1) as usize;
+        let batch = std::cmp::min(odd_count, SEGMENT * rayon::current_num_threads());
+        let segments = (0..batch.div_ceil(SEGMENT))
+            .into_par_iter()
+            .map(|k| {
+                let len = std::cmp::min(SEGMENT, batch - k * SEGMENT);
+                let lo = self.lo + 2 * (k * SEGMENT) as u64;
+                sieve_segment(lo, len, &self.base, &self.pattern)
+            })
+            .collect::<Vec<Vec<u64>>>();
+        self.lo += 2 * batch as u64;
+        self.buffer = segments.concat().into_iter();
+    }
+}
+
+impl Iterator for PrimeStream {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            if let Some(p) = self.buffer.next() {
+                return Some(p);
+            }
+            if self.lo > self.hi {
+                return None;
+            }
+            self.refill();
+        }
+    }
+}
+
+///
+/// Primes in [lo, hi], see `PrimeStream`
+///
+pub fn segmented_sieve(lo: u64, hi: u64) -> Vec<u64> {
+    PrimeStream::new(lo, hi).collect()
+}
+
+///
+/// Primes in [start, end]. Ranges below 10¹⁶ are sieved exactly; beyond that every
+/// odd number is tested with Baillie-PSW, which has no known counterexample.
+///
+pub fn primes_in_range(start: &BigInt, end: &BigInt) -> Vec<BigInt> {
+    let start = std::cmp::max(start.clone(), BigInt::zero());
+    if end < &start {
+        return Vec::new();
+    }
+    if end < &BigInt::from(SIEVE_LIMIT) {
+        return segmented_sieve(start.to_u64().unwrap(), end.to_u64().unwrap())
+            .into_iter()
+            .map(BigInt::from)
+            .collect();
+    }
+
+    let mut primes = range_inclusive(start, end.clone())
+        .par_bridge()
+        .filter(baillie_psw)
+        .collect::<Vec<BigInt>>();
+    primes.sort();
+    primes
+}
+
+///
+/// Composite numbers in [start, end], the complement of the primes in that range
+/// leaving out 0 and 1.
+///
+pub fn composites_in_range(start: &BigInt, end: &BigInt) -> Vec<BigInt> {
+    let start = std::cmp::max(start.clone(), BigInt::from(4u64));
+    let mut primes = primes_in_range(&start, end).into_iter().peekable();
+    let mut composites = Vec::new();
+    for n in range_inclusive(start, end.clone()) {
+        if primes.peek() == Some(&n) {
+            primes.next();
+        } else {
+            composites.push(n);
+        }
+    }
+    composites
+}
+
+///
+/// Smallest prime >= n. Windows from n on are sieved until one contains a prime.
+///
+pub fn next_prime(n: &BigInt) -> BigInt {
+    if n < &BigInt::from(2u64) {
+        return BigInt::from(2u64);
+    }
+    let window = BigInt::from(1u64 << 16);
+    let mut lo = n.clone();
+    loop {
+        let hi = &lo + &window;
+        if hi >= BigInt::from(SIEVE_LIMIT) {
+            break;
+        }
+        if let Some(p) = primes_in_range(&lo, &hi).into_iter().next() {
+            return p;
+        }
+        lo = hi + 1u32;
+    }
+
+    // beyond the sieve limit, test the odd numbers in turn
+    if lo.is_even() {
+        lo += 1u32;
+    }
+    while !baillie_psw(&lo) {
+        lo += 2u32;
+    }
+    lo
+}
+
+///
+/// π(x), the number of primes <= x, by the Lucy_Hedgehog method in O(x³ᐟ⁴) time and
+/// O(√x) memory. S(v) starts as the count of 2..=v and, for each prime p <= √x, the
+/// numbers whose smallest prime factor is p are removed from every S(v) with v >= p²:
+/// S(v) -= S(v/p) - S(p - 1). Only the values v = ⌊x/k⌋ are ever needed.
+///
+pub fn prime_count(x: u64) -> u64 {
+    if x < 2 {
+        return 0;
+    }
+    let r = x.sqrt();
+    // small[v] = S(v) for v <= r, large[i] = S(x/i) for i <= r
+    let mut small = (0..=r).map(|v| v.saturating_sub(1)).collect::<Vec<u64>>();
+    let mut large = (0..=r)
+        .map(|i| x.checked_div(i).map_or(0, |v| v - 1))
+        .collect::<Vec<u64>>();
+
+    for p in 2..=r {
+        if small[p as usize] == small[p as usize - 1] {
+            // p is not prime
+            continue;
+        }
+        let count_below_p = small[p as usize - 1];
+        let p2 = p * p;
+        for i in 1..=std::cmp::min(r, x / p2) {
+            let d = i * p;
+            let s_x_over_d = if d <= r {
+                large[d as usize]
+            } else {
+                small[(x / d) as usize]
+            };
+            large[i as usize] -= s_x_over_d - count_below_p;
+        }
+        for v in (p2..=r).rev() {
+            small[v as usize] -= small[(v / p) as usize] - count_below_p;
+        }
+    }
+    large[1]
+}
+
+///
+/// Logarithmic integral li(x) by Ramanujan's series
+/// li(x) = γ + ln ln x + √x Σ (-1)ⁿ⁻¹ (ln x)ⁿ / (n! 2ⁿ⁻¹) Σ_{k <= (n-1)/2} 1/(2k + 1)
+///
+pub fn logarithmic_integral(x: f64) -> f64 {
+    const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+    let ln_x = x.ln();
+    let (mut sum, mut term, mut inner) = (0.0, 1.0, 0.0);
+    for n in 1..200 {
+        // term = (ln x)ⁿ / (n! 2ⁿ⁻¹)
+        term *= ln_x / n as f64 / if n == 1 { 1.0 } else { 2.0 };
+        if (n - 1) % 2 == 0 {
+            inner += 1.0 / n as f64;
+        }
+        let sign = if n % 2 == 1 { 1.0 } else { -1.0 };
+        sum += sign * term * inner;
+        if term * inner < 1e-17 * sum.abs() {
+            break;
+        }
+    }
+    EULER_GAMMA + ln_x.ln() + x.sqrt() * sum
+}
+
+///
+/// The nth prime pₙ. An estimate x from Cipolla's expansion is refined by Newton's
+/// method on li(x) ≈ π(x), π is computed exactly a little below x and the primes from
+/// there are streamed until the count reaches n.
+///
+pub fn nth_prime(n: u64) -> Result<u64, String> {
+    if n == 0 {
+        return Err("n must be at least 1\n".to_string());
+    }
+    if n > NTH_PRIME_LIMIT {
+        return Err(format!(
+            "the {}th prime is beyond the prime count limit 10¹⁴, n must be at most {}\n",
+            n, NTH_PRIME_LIMIT
+        ));
+    }
+    if n < 1000 {
+        // p₁₀₀₀ = 7919
+        return Ok(PrimeStream::new(2, 7919).nth(n as usize - 1).unwrap());
+    }
+
+    let n_f = n as f64;
+    let (ln_n, ln_ln_n) = (n_f.ln(), n_f.ln().ln());
+    // pₙ < n(ln n + ln ln n) for n >= 6
+    let upper = (n_f * (ln_n + ln_ln_n)) as u64;
+    let mut x = n_f * (ln_n + ln_ln_n - 1.0 + (ln_ln_n - 2.0) / ln_n);
+    for _ in 0..3 {
+        x -= (logarithmic_integral(x) - n_f) * x.ln();
+    }
+
+    // |li(x) - π(x)| is well below √x.ln x in this range
+    let margin = (x.sqrt() * x.ln()) as u64;
+    let mut lo = (x as u64).saturating_sub(margin);
+    let mut count = prime_count(lo);
+    while count >= n {
+        lo = lo.saturating_sub(margin);
+        count = prime_count(lo);
+    }
+    Ok(PrimeStream::new(lo + 1, upper)
+        .nth((n - count - 1) as usize)
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primality::is_prime_trial_division;
+    use num_traits::One;
+
+    #[test]
+    fn test_small_primes() {
+        assert_eq!(small_primes(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    }
+
+    #[test]
+    fn test_segmented_sieve() {
+        let expected = (0..20000u64)
+            .filter(|n| is_prime_trial_division(&BigInt::from(*n)))
+            .collect::<Vec<u64>>();
+        assert_eq!(segmented_sieve(0, 19999), expected);
+        assert_eq!(segmented_sieve(7, 30), vec![7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(segmented_sieve(24, 28), Vec::<u64>::new());
+
+        // a window spanning several segments far from 0
+        let (lo, hi) = (1_000_000_000_000u64, 1_000_000_600_000u64);
+        let primes = segmented_sieve(lo, hi);
+        let expected = (lo..=hi)
+            .filter(|n| baillie_psw(&BigInt::from(*n)))
+            .collect::<Vec<u64>>();
+        assert_eq!(primes, expected);
+    }
+
+    #[test]
+    fn test_composites_in_range() {
+        let composites = composites_in_range(&BigInt::from(0u64), &BigInt::from(12u64));
+        assert_eq!(
+            composites,
+            [4u64, 6, 8, 9, 10, 12]
+                .into_iter()
+                .map(BigInt::from)
+                .collect::<Vec<BigInt>>()
+        );
+    }
+
+    #[test]
+    fn test_prime_count() {
+        let expected = (0..2000u64)
+            .map(|x| segmented_sieve(0, x).len() as u64)
+            .collect::<Vec<u64>>();
+        assert_eq!(
+            (0..2000u64).map(prime_count).collect::<Vec<u64>>(),
+            expected
+        );
+        assert_eq!(prime_count(1_000_000), 78498);
+        assert_eq!(prime_count(10_000_000_000), 455052511);
+    }
+
+    #[test]
+    fn test_nth_prime() {
+        assert_eq!(nth_prime(1), Ok(2));
+        assert_eq!(nth_prime(10), Ok(29));
+        assert_eq!(nth_prime(1000), Ok(7919));
+        assert_eq!(nth_prime(1_000_000), Ok(15485863));
+        assert_eq!(nth_prime(100_000_000), Ok(2038074743));
+        assert!(nth_prime(0).is_err());
+        assert!(nth_prime(NTH_PRIME_LIMIT + 1).is_err());
+    }
+
+    #[test]
+    fn test_logarithmic_integral() {
+        assert!((logarithmic_integral(1e6) - 78627.549159).abs() < 1e-4);
+        assert!((logarithmic_integral(2.0) - 1.045163780117).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_next_prime() {
+        assert_eq!(next_prime(&BigInt::from(0u64)), BigInt::from(2u64));
+        assert_eq!(next_prime(&BigInt::from(14u64)), BigInt::from(17u64));
+        // 10¹⁵ + 37 is the first prime after 10¹⁵
+        let n = BigInt::from(10u64).pow(15);
+        assert_eq!(next_prime(&n), &n + 37u32);
+        // beyond the sieve limit, 10²⁰ + 39
+        let n = BigInt::from(10u64).pow(20);
+        assert_eq!(next_prime(&n), &n + 39u32);
+    }
+
+    #[test]
+    fn edge_case_two() {
+        assert_eq!(next_prime(&BigInt::from(2u64)), BigInt::from(2u64));
+    }
+
+    #[test]
+    fn finds_small_primes() {
+        let primes = vec![
+            BigInt::from(5u64),
+            BigInt::from(7u64),
+            BigInt::from(11u64),
+            BigInt::from(13u64),
+            BigInt::from(17u64),
+            BigInt::from(19u64),
+            BigInt::from(23u64),
+            BigInt::from(29u64),
+        ];
+        assert_eq!(
+            primes,
+            primes
+                .iter()
+                .map(|x| next_prime(&(x - BigInt::one())))
+                .collect::<Vec<BigInt>>()
+        );
+    }
+
+    #[test]
+    fn returns_argument_when_it_is_already_prime() {
+        assert_eq!(next_prime(&BigInt::from(101)), BigInt::from(101));
+    }
+}
@@ -0,0 +1,791 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::OpenOptions,
+    io::{BufRead, BufReader, Write},
+    time::{Duration, Instant},
+};
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use rand::{rngs::StdRng, seq::SliceRandom};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    display::QsDependencyTable,
+    gf2::{Gf2Nullspace, SparseMatrixGf2},
+    sieve::PrimeStream,
+    utils::{jacobi_symbol, sqrt_mod_prime, Gcd},
+};
+
+/// (bits of kn, factor base size, M, large prime bound / largest prime of the factor base),
+/// the parameters in between are interpolated on the factor base size.
+const SIQS_PARAMETERS: [(u64, usize, u32, u64); 10] = [
+    (64, 100, 32768, 40),
+    (128, 450, 32768, 40),
+    (183, 2000, 65536, 40),
+    (200, 3000, 65536, 50),
+    (212, 5400, 98304, 50),
+    (233, 10000, 98304, 100),
+    (249, 27000, 98304, 100),
+    (266, 50000, 98304, 100),
+    (283, 55000, 196608, 80),
+    (298, 60000, 196608, 80),
+];
+/// Squarefree odd multipliers k tried by Knuth-Schroeppel.
+const SIQS_MULTIPLIERS: [u64; 31] = [
+    1, 3, 5, 7, 11, 13, 15, 17, 19, 21, 23, 29, 31, 33, 35, 37, 39, 41, 43, 47, 51, 53, 55, 57, 59,
+    61, 65, 67, 69, 71, 73,
+];
+/// Primes below this are not sieved, only trial divided, they cost the most and add the least.
+const SIQS_SMALL_PRIME: u32 = 30;
+/// Bits below log₂|Q(x)| - log₂(large prime bound) still accepted by the sieve.
+const SIQS_FUDGE: f64 = 4.0;
+/// Relations collected beyond the number of columns of the matrix.
+const SIQS_EXTRA_RELATIONS: usize = 64;
+/// Smallest factor base accepted, smaller ones leave too few primes for A and too few
+/// smooth values to ever fill the matrix.
+pub const SIQS_MIN_FACTOR_BASE: usize = 60;
+/// Draws of A tried before the window is taken as exhausted.
+const SIQS_A_ATTEMPTS: usize = 1000;
+/// Batches in a row without a new relation before the run is abandoned.
+const SIQS_STALLED_BATCHES: usize = 50;
+
+///
+/// Relation collection so far, passed to the progress callback after every batch of
+/// polynomials
+///
+#[derive(Clone, Debug)]
+pub struct SiqsProgress {
+    pub polynomials: u64,
+    pub full: usize,
+    pub combined: usize,
+    pub partials: usize,
+    pub needed: usize,
+    pub elapsed: Duration,
+}
+
+///
+/// A run of SIQS: the multiplier k, the parameters, the relations, the reduction of the
+/// matrix and the dependencies tried in the square root step
+///
+#[derive(Clone, Debug)]
+pub struct SiqsTrace {
+    pub multiplier: u64,
+    pub factor_base: usize,
+    pub largest_prime: u32,
+    pub m: u32,
+    pub a_primes: usize,
+    pub large_prime_bound: u64,
+    pub resumed: usize,
+    pub progress: SiqsProgress,
+    pub matrix: Option<Gf2Nullspace>,
+    pub dependencies: Vec<QsDependencyTable>,
+    pub factor: Option<BigInt>,
+}
+
+///
+/// v² ≡ y (mod n) with y = ±∏pᵉ.L, the factors as (index, e), index 0 standing for -1
+/// and index i for the i-th prime of the factor base. L = 1 for a full relation, a
+/// large prime for a partial one and the square root of L² for two partials combined.
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Relation {
+    v: String,
+    factors: Vec<(u32, u32)>,
+    large: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+struct CheckpointHeader {
+    n: String,
+    multiplier: u64,
+    factor_base: usize,
+}
+
+struct FactorBasePrime {
+    p: u32,
+    /// √kn (mod p)
+    t: u32,
+    log: u8,
+}
+
+///
+/// Everything the workers share: n, kn, the factor base and the sieve parameters
+///
+struct Siqs {
+    n: BigInt,
+    kn: BigInt,
+    base: Vec<FactorBasePrime>,
+    m: u32,
+    large_bound: u64,
+    threshold: u8,
+    /// the first prime that is sieved
+    small: usize,
+    a_primes: usize,
+    /// factor base indices the primes of A are drawn from, only primes with √kn (mod p)
+    window: Vec<usize>,
+    log_target: f64,
+}
+
+///
+/// a⁻¹ (mod p) by the extended Euclidean algorithm
+///
+fn inverse_mod(a: u64, p: u64) -> u64 {
+    let (mut r0, mut r1, mut s0, mut s1) = (p as i64, (a % p) as i64, 0i64, 1i64);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, s0 - q * s1);
+    }
+    s0.rem_euclid(p as i64) as u64
+}
+
+///
+/// Knuth-Schroeppel: the k maximising Σ g(p, kn) ln p - ½ ln k over the small primes,
+/// where g = 2/(p - 1) when (kn/p) = 1 and 1/p when p | k. A k that makes many small
+/// primes residues makes Q(x) smooth more often.
+///
+fn multiplier(n: &BigInt) -> u64 {
+    let primes = PrimeStream::new(3, 1000).collect::<Vec<u64>>();
+    let score = |k: u64| {
+        let kn = n * k;
+        let mut f = -0.5 * (k as f64).ln();
+        f += std::f64::consts::LN_2
+            * match (&kn % 8u32).to_u64().expect("residue") {
+                1 => 2.0,
+                5 => 1.0,
+                _ => 0.5,
+            };
+        for &p in &primes {
+            let ln_p = (p as f64).ln();
+            if k.is_multiple_of(p) {
+                f += ln_p / p as f64;
+            } else if jacobi_symbol(&kn, &BigInt::from(p)) == 1 {
+                f += 2.0 * ln_p / (p - 1) as f64;
+            }
+        }
+        f
+    };
+    SIQS_MULTIPLIERS
+        .into_iter()
+        .map(|k| (k, score(k)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(1, |(k, _)| k)
+}
+
+impl Siqs {
+    ///
+    /// Err(p) when a prime of the factor base divides n
+    ///
+    fn new(n: &BigInt, k: u64, fb_size: Option<usize>) -> Result<Self, u64> {
+        let kn = n * k;
+        let bits = kn.bits();
+        let i = SIQS_PARAMETERS
+            .iter()
+            .position(|(b, ..)| *b >= bits)
+            .unwrap_or(SIQS_PARAMETERS.len() - 1);
+        let (size, m, large_mult) = match i {
+            0 => (
+                SIQS_PARAMETERS[0].1,
+                SIQS_PARAMETERS[0].2,
+                SIQS_PARAMETERS[0].3,
+            ),
+            _ => {
+                let (b0, s0, ..) = SIQS_PARAMETERS[i - 1];
+                let (b1, s1, m, l) = SIQS_PARAMETERS[i];
+                let f = (bits.clamp(b0, b1) - b0) as f64 / (b1 - b0) as f64;
+                (s0 + ((s1 - s0) as f64 * f) as usize, m, l)
+            }
+        };
+        let size = fb_size.unwrap_or(size);
+
+        let mut base = vec![FactorBasePrime { p: 2, t: 1, log: 1 }];
+        if n.is_even() {
+            return Err(2);
+        }
+        for p in PrimeStream::new(3, u32::MAX as u64) {
+            if base.len() >= size {
+                break;
+            }
+            let p_big = BigInt::from(p);
+            if (n % &p_big).is_zero() {
+                return Err(p);
+            }
+            let t = match jacobi_symbol(&kn, &p_big) {
+                0 => 0,
+                1 => sqrt_mod_prime(&(&kn % &p_big), &p_big)
+                    .and_then(|t| t.to_u32())
+                    .expect("kn is a quadratic residue mod p"),
+                _ => continue,
+            };
+            base.push(FactorBasePrime {
+                p: p as u32,
+                t,
+                log: (p as f64).log2().round() as u8,
+            });
+        }
+        let small = base.partition_point(|f| f.p < SIQS_SMALL_PRIME);
+        let largest = base[base.len() - 1].p as u64;
+        let large_bound = largest * large_mult;
+
+        // A ≈ √(2kn)/M makes |Q(x)| <= M√(kn/2) on [-M, M)
+        let ln_kn = kn.bits() as f64 * std::f64::consts::LN_2;
+        let log_target = (std::f64::consts::LN_2 + ln_kn) / 2.0 - (m as f64).ln();
+        let log_q = log_target.min(ln_kn / 2.0);
+        let mut a_primes = ((log_q / 2000f64.ln()).round() as usize).max(1);
+        while a_primes > 1 && (log_q / a_primes as f64).exp() < base[small].p as f64 * 4.0 {
+            a_primes -= 1;
+        }
+        while (log_q / a_primes as f64).exp() > largest as f64 / 2.0 {
+            a_primes += 1;
+        }
+        let ideal = (log_q / a_primes as f64).exp();
+        let lo = base
+            .partition_point(|f| (f.p as f64) < ideal / 2.0)
+            .max(small);
+        let hi = base
+            .partition_point(|f| (f.p as f64) < ideal * 2.0)
+            .max(lo + 4 * a_primes)
+            .min(base.len());
+        let usable =
+            |lo: usize, hi: usize| (lo..hi).filter(|&i| base[i].t != 0).collect::<Vec<usize>>();
+        // near the end of a small factor base the window can run short, fall back to
+        // every sieved prime
+        let mut window = usable(lo, hi);
+        if window.len() < 2 * a_primes {
+            window = usable(small, base.len());
+        }
+
+        let log_q_x = (m as f64).log2() + kn.bits() as f64 / 2.0 - 0.5;
+        let threshold = (log_q_x - (large_bound as f64).log2() - SIQS_FUDGE).max(1.0) as u8;
+        Ok(Self {
+            n: n.clone(),
+            kn,
+            base,
+            m,
+            large_bound,
+            threshold,
+            small,
+            a_primes,
+            window,
+            log_target: log_q,
+        })
+    }
+
+    ///
+    /// The factor base indices of the primes of A: s - 1 drawn at random from the window
+    /// and the last one that brings A closest to √(2kn)/M. None when the window has too
+    /// few primes or no draw works out.
+    ///
+    fn choose_a(&self, rng: &mut StdRng) -> Option<Vec<usize>> {
+        let s = self.a_primes;
+        if self.window.len() < s {
+            return None;
+        }
+        for _ in 0..SIQS_A_ATTEMPTS {
+            let mut q = self
+                .window
+                .choose_multiple(rng, s - 1)
+                .copied()
+                .collect::<Vec<usize>>();
+            let log_a = q.iter().map(|&i| (self.base[i].p as f64).ln()).sum::<f64>();
+            let last = if s == 1 {
+                *self.window.choose(rng)?
+            } else {
+                let ideal = (self.log_target - log_a).exp();
+                self.base
+                    .partition_point(|f| (f.p as f64) < ideal)
+                    .clamp(self.small, self.base.len() - 1)
+            };
+            if self.base[last].t != 0 && !q.contains(&last) {
+                q.push(last);
+                q.sort();
+                return Some(q);
+            }
+        }
+        None
+    }
+
+    ///
+    /// Sieve the 2ˢ⁻¹ polynomials Q(x) = ((Ax + B)² - kn)/A sharing A, switching B by the
+    /// Gray code. Returns the full and the partial relations.
+    ///
+    fn sieve_family(&self, q: &[usize]) -> Vec<Relation> {
+        let (n, kn, base) = (&self.n, &self.kn, &self.base);
+        let np = base.len();
+        let a = q.iter().fold(BigInt::one(), |acc, &i| acc * base[i].p);
+
+        // B = B₀ ± B₁ ± ... with Bₗ² ≡ kn (mod qₗ) and Bₗ ≡ 0 (mod qⱼ), j ≠ l
+        let b_terms = q
+            .iter()
+            .map(|&i| {
+                let p = base[i].p as u64;
+                let a_over_q = &a / p;
+                let inv = inverse_mod((&a_over_q % p).to_u64().expect("residue"), p);
+                let mut gamma = base[i].t as u64 * inv % p;
+                if gamma > p / 2 {
+                    gamma = p - gamma;
+                }
+                a_over_q * gamma
+            })
+            .collect::<Vec<BigInt>>();
+        let mut b = b_terms.iter().sum::<BigInt>();
+
+        let mut in_a = vec![false; np];
+        q.iter().for_each(|&i| in_a[i] = true);
+        let mut soln = vec![(0u32, 0u32); np];
+        let mut bainv2 = vec![vec![0u32; np]; b_terms.len()];
+        for (i, f) in base.iter().enumerate() {
+            if in_a[i] {
+                continue;
+            }
+            let p = f.p as u64;
+            let ainv = inverse_mod((&a % p).to_u64().expect("residue"), p);
+            for (l, b_l) in b_terms.iter().enumerate() {
+                bainv2[l][i] = (2 * (b_l % p).to_u64().expect("residue") * ainv % p) as u32;
+            }
+            let b_p = b.mod_floor(&BigInt::from(p)).to_u64().expect("residue");
+            let t = f.t as u64;
+            soln[i] = (
+                (ainv * ((t + p - b_p) % p) % p) as u32,
+                (ainv * ((2 * p - t - b_p) % p) % p) as u32,
+            );
+        }
+
+        let m = self.m as i64;
+        let len = 2 * self.m as usize;
+        let mut sieve = vec![0u8; len];
+        let mut relations = Vec::new();
+        let families = 1u64 << (q.len() - 1);
+        for j in 0..families {
+            if j > 0 {
+                // Gray code: bit v flips, B moves by ∓2Bᵥ₊₁ and the roots by ±2Bᵥ₊₁A⁻¹
+                let v = j.trailing_zeros() as usize;
+                let minus = (j ^ (j >> 1)) >> v & 1 == 1;
+                if minus {
+                    b -= &b_terms[v + 1] * 2u32;
+                } else {
+                    b += &b_terms[v + 1] * 2u32;
+                }
+                for (i, f) in base.iter().enumerate() {
+                    if in_a[i] {
+                        continue;
+                    }
+                    let (p, d) = (f.p, bainv2[v + 1][i]);
+                    let step = |r: u32| match minus {
+                        true => ((r as u64 + d as u64) % p as u64) as u32,
+                        false => ((r as u64 + p as u64 - d as u64) % p as u64) as u32,
+                    };
+                    soln[i] = (step(soln[i].0), step(soln[i].1));
+                }
+            }
+
+            sieve.iter_mut().for_each(|x| *x = 0);
+            let mut offsets = vec![(0u32, 0u32); np];
+            for (i, f) in base.iter().enumerate() {
+                if in_a[i] {
+                    continue;
+                }
+                let p = f.p as usize;
+                let r1 = (soln[i].0 as usize + self.m as usize) % p;
+                let r2 = (soln[i].1 as usize + self.m as usize) % p;
+                offsets[i] = (r1 as u32, r2 as u32);
+                if i < self.small {
+                    continue;
+                }
+                for r in [r1, r2].iter().take(if r1 == r2 { 1 } else { 2 }) {
+                    let mut k = *r;
+                    while k < len {
+                        sieve[k] = sieve[k].wrapping_add(f.log);
+                        k += p;
+                    }
+                }
+            }
+
+            // C = (B² - kn)/A, Q(x) = Ax² + 2Bx + C and (Ax + B)² = A.Q(x) + kn
+            let c = (&b * &b - kn) / &a;
+            for (idx, s) in sieve.iter().enumerate() {
+                if *s < self.threshold {
+                    continue;
+                }
+                let x = idx as i64 - m;
+                let q_x = (&a * x + &b * 2u32) * x + &c;
+                if q_x.is_zero() {
+                    continue;
+                }
+                let mut factors: Vec<(u32, u32)> = Vec::new();
+                if q_x.is_negative() {
+                    factors.push((0, 1));
+                }
+                let mut rest = q_x.abs();
+                for (i, f) in base.iter().enumerate() {
+                    let hit = i < self.small
+                        || in_a[i]
+                        || idx as u32 % f.p == offsets[i].0
+                        || idx as u32 % f.p == offsets[i].1;
+                    let mut e = u32::from(in_a[i]);
+                    if hit {
+                        loop {
+                            let (quotient, r) = rest.div_rem(&BigInt::from(f.p));
+                            if !r.is_zero() {
+                                break;
+                            }
+                            rest = quotient;
+                            e += 1;
+                        }
+                    }
+                    if e > 0 {
+                        factors.push((i as u32 + 1, e));
+                    }
+                }
+                let large = match rest.to_u64() {
+                    Some(l) if l < self.large_bound => l,
+                    _ => continue,
+                };
+                relations.push(Relation {
+                    v: (&a * x + &b).mod_floor(n).to_string(),
+                    factors,
+                    large,
+                });
+            }
+        }
+        relations
+    }
+}
+
+///
+/// Full relations, partials grouped by their large prime and the relations combined
+/// from them: r₁ with each later rᵢ sharing L, y₁yᵢ = (...).L²
+///
+#[derive(Default)]
+struct Relations {
+    full: Vec<Relation>,
+    partials: HashMap<u64, Vec<Relation>>,
+    combined: Vec<Relation>,
+    seen: HashSet<String>,
+}
+
+impl Relations {
+    fn add(&mut self, relation: Relation, n: &BigInt) -> bool {
+        if !self.seen.insert(relation.v.clone()) {
+            return false;
+        }
+        if relation.large == 1 {
+            self.full.push(relation);
+            return true;
+        }
+        let group = self.partials.entry(relation.large).or_default();
+        if let Some(first) = group.first() {
+            let v = (first.v.parse::<BigInt>().expect("relation")
+                * relation.v.parse::<BigInt>().expect("relation"))
+                % n;
+            let mut exponents: HashMap<u32, u32> = HashMap::new();
+            for (i, e) in first.factors.iter().chain(&relation.factors) {
+                *exponents.entry(*i).or_default() += e;
+            }
+            let mut factors = exponents.into_iter().collect::<Vec<(u32, u32)>>();
+            factors.sort();
+            self.combined.push(Relation {
+                v: v.to_string(),
+                factors,
+                large: relation.large,
+            });
+        }
+        group.push(relation);
+        true
+    }
+
+    fn progress(&self, polynomials: u64, needed: usize, start: &Instant) -> SiqsProgress {
+        SiqsProgress {
+            polynomials,
+            full: self.full.len(),
+            combined: self.combined.len(),
+            partials: self.partials.values().map(Vec::len).sum(),
+            needed,
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+///
+/// The relations saved for n, k and the factor base size, or none when the file does not
+/// exist yet. A file written for another n or other parameters is an error.
+///
+fn load_checkpoint(path: &str, header: &CheckpointHeader) -> Result<Vec<Relation>, String> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("error: {}: {}\n", path, e)),
+    };
+    let mut lines = BufReader::new(file).lines();
+    let first = match lines.next() {
+        Some(line) => line.map_err(|e| format!("error: {}: {}\n", path, e))?,
+        None => return Ok(Vec::new()),
+    };
+    let saved: CheckpointHeader =
+        serde_json::from_str(&first).map_err(|e| format!("error: {}: {}\n", path, e))?;
+    if &saved != header {
+        return Err(format!(
+            "error: {} holds relations for n = {}, k = {} with {} primes\n",
+            path, saved.n, saved.multiplier, saved.factor_base
+        ));
+    }
+    lines
+        .map(|line| {
+            let line = line.map_err(|e| format!("error: {}: {}\n", path, e))?;
+            serde_json::from_str(&line).map_err(|e| format!("error: {}: {}\n", path, e))
+        })
+        .collect()
+}
+
+///
+/// Appends the relations to the checkpoint, writing the header first if it is new
+///
+fn save_checkpoint(
+    path: &str,
+    header: &CheckpointHeader,
+    relations: &[Relation],
+) -> Result<(), String> {
+    let exists = std::path::Path::new(path).exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("error: {}: {}\n", path, e))?;
+    let mut lines = String::new();
+    if !exists {
+        lines.push_str(&serde_json::to_string(header).map_err(|e| e.to_string())?);
+        lines.push('\n');
+    }
+    for relation in relations {
+        lines.push_str(&serde_json::to_string(relation).map_err(|e| e.to_string())?);
+        lines.push('\n');
+    }
+    file.write_all(lines.as_bytes())
+        .map_err(|e| format!("error: {}: {}\n", path, e))
+}
+
+///
+/// The self-initialising quadratic sieve. Returns a nontrivial factor of the odd
+/// composite n, not a prime power, with the parameters, the relation counts and the
+/// dependencies tried.
+///
+/// The sieve polynomial is Q(x) = ((Ax + B)² - kn)/A on [-M, M) with A = q₁ ... qₛ a
+/// product of factor base primes near √(2kn)/M. The 2ˢ⁻¹ choices of B = B₀ ± ... ± Bₛ₋₁
+/// share A, so A⁻¹ (mod p) is computed once per A and each switch of B, in Gray code
+/// order, moves every root by a precomputed 2BᵥA⁻¹ (mod p). A y with a single prime
+/// L above the factor base is kept, and two with the same L make a relation.
+///
+/// Batches of A are sieved in parallel, one per thread; after each batch the new
+/// relations are appended to the checkpoint and progress is reported. A run restarted
+/// with the same checkpoint picks up its relations. The relations are solved with
+/// the Gaussian elimination of the quadratic sieve and X² ≡ Y² (mod n) gives the factor.
+///
+pub fn siqs(
+    n: &BigInt,
+    fb_size: Option<usize>,
+    checkpoint: Option<&str>,
+    rng: &mut StdRng,
+    report: impl Fn(&SiqsProgress),
+) -> Result<SiqsTrace, String> {
+    if fb_size.is_some_and(|size| size < SIQS_MIN_FACTOR_BASE) {
+        return Err(format!(
+            "error: the factor base needs at least {} primes\n",
+            SIQS_MIN_FACTOR_BASE
+        ));
+    }
+    let start = Instant::now();
+    let k = multiplier(n);
+    let mut trace = SiqsTrace {
+        multiplier: k,
+        factor_base: 0,
+        largest_prime: 0,
+        m: 0,
+        a_primes: 0,
+        large_prime_bound: 0,
+        resumed: 0,
+        progress: Relations::default().progress(0, 0, &start),
+        matrix: None,
+        dependencies: Vec::new(),
+        factor: None,
+    };
+    let sqrt_n = n.sqrt();
+    if &(&sqrt_n * &sqrt_n) == n {
+        trace.factor = Some(sqrt_n);
+        return Ok(trace);
+    }
+    let ctx = match Siqs::new(n, k, fb_size) {
+        Ok(ctx) => ctx,
+        Err(p) => {
+            trace.factor = Some(BigInt::from(p));
+            return Ok(trace);
+        }
+    };
+    trace.factor_base = ctx.base.len();
+    trace.largest_prime = ctx.base[ctx.base.len() - 1].p;
+    trace.m = ctx.m;
+    trace.a_primes = ctx.a_primes;
+    trace.large_prime_bound = ctx.large_bound;
+
+    let header = CheckpointHeader {
+        n: n.to_string(),
+        multiplier: k,
+        factor_base: ctx.base.len(),
+    };
+    let mut relations = Relations::default();
+    if let Some(path) = checkpoint {
+        for relation in load_checkpoint(path, &header)? {
+            relations.add(relation, n);
+        }
+        trace.resumed = relations.seen.len();
+    }
+
+    // columns: -1 and the factor base
+    let needed = ctx.base.len() + 1 + SIQS_EXTRA_RELATIONS;
+    let mut polynomials = 0u64;
+    let mut used: HashSet<Vec<usize>> = HashSet::new();
+    let mut stalled = 0;
+    while relations.full.len() + relations.combined.len() < needed {
+        let batch = (0..rayon::current_num_threads())
+            .filter_map(|_| {
+                (0..SIQS_A_ATTEMPTS)
+                    .filter_map(|_| ctx.choose_a(rng))
+                    .find(|q| used.insert(q.clone()))
+            })
+            .collect::<Vec<Vec<usize>>>();
+        if batch.is_empty() {
+            return Err(format!(
+                "error: no unused A left after {} polynomials, {} of {} relations\n",
+                polynomials,
+                relations.full.len() + relations.combined.len(),
+                needed
+            ));
+        }
+        let before = relations.full.len() + relations.combined.len();
+        let found = batch
+            .par_iter()
+            .map(|q| ctx.sieve_family(q))
+            .collect::<Vec<Vec<Relation>>>();
+        polynomials += batch.iter().map(|q| 1u64 << (q.len() - 1)).sum::<u64>();
+
+        let new = found
+            .into_iter()
+            .flatten()
+            .filter(|relation| relations.add(relation.clone(), n))
+            .collect::<Vec<Relation>>();
+        if let Some(path) = checkpoint {
+            save_checkpoint(path, &header, &new)?;
+        }
+        report(&relations.progress(polynomials, needed, &start));
+
+        if relations.full.len() + relations.combined.len() > before {
+            stalled = 0;
+        } else {
+            stalled += 1;
+        }
+        if stalled >= SIQS_STALLED_BATCHES {
+            return Err(format!(
+                "error: no new relations in {} batches after {} polynomials, {} of {} relations\n",
+                SIQS_STALLED_BATCHES, polynomials, before, needed
+            ));
+        }
+    }
+    trace.progress = relations.progress(polynomials, needed, &start);
+
+    // square root step
+    let all = relations
+        .full
+        .iter()
+        .chain(&relations.combined)
+        .collect::<Vec<&Relation>>();
+    let mut matrix = SparseMatrixGf2::new(ctx.base.len() + 1);
+    for r in &all {
+        matrix.push_row(
+            r.factors
+                .iter()
+                .filter(|(_, e)| e % 2 == 1)
+                .map(|&(i, _)| i),
+        );
+    }
+    let nullspace = matrix.nullspace();
+    for (d, dependency) in nullspace.dependencies.iter().enumerate() {
+        let mut x = BigInt::one();
+        let mut y = BigInt::one();
+        let mut exponents: HashMap<u32, u32> = HashMap::new();
+        for &r in dependency {
+            x = x * all[r].v.parse::<BigInt>().expect("relation") % n;
+            y = y * all[r].large % n;
+            for (i, e) in &all[r].factors {
+                *exponents.entry(*i).or_default() += e;
+            }
+        }
+        for (i, e) in exponents {
+            if i > 0 {
+                let p = BigInt::from(ctx.base[i as usize - 1].p);
+                y = y * p.modpow(&BigInt::from(e / 2), n) % n;
+            }
+        }
+        let g = (&x - &y).mod_floor(n).gcd_euclid(n);
+        trace.dependencies.push(QsDependencyTable::new(
+            (d + 1).to_string(),
+            dependency.len().to_string(),
+            x.to_string(),
+            y.to_string(),
+            g.to_string(),
+        ));
+        if !g.is_one() && &g != n {
+            trace.factor = Some(g);
+            break;
+        }
+    }
+    trace.matrix = Some(nullspace);
+    Ok(trace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::seeded_rng;
+
+    #[test]
+    fn test_inverse_mod() {
+        assert_eq!(inverse_mod(3, 7), 5);
+        assert_eq!(inverse_mod(10, 17) * 10 % 17, 1);
+    }
+
+    #[test]
+    fn test_siqs() {
+        let (_, mut rng) = seeded_rng(Some(1));
+        // (10¹⁴ + 31)(3.10¹⁵ + 37)
+        let n = "300000000000096700000000001147".parse::<BigInt>().unwrap();
+        let trace = siqs(&n, None, None, &mut rng, |_| {}).unwrap();
+        let d = trace.factor.unwrap();
+        assert!(d.to_string() == "100000000000031" || d.to_string() == "3000000000000037");
+        assert!(siqs(&n, Some(20), None, &mut rng, |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_siqs_checkpoint() {
+        let (_, mut rng) = seeded_rng(Some(2));
+        // (10¹⁷ + 3)(7.10¹⁷ + 33)
+        let n = "70000000000000005400000000000000099"
+            .parse::<BigInt>()
+            .unwrap();
+        let path = std::env::temp_dir().join("nt-tools-test-siqs.jsonl");
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let trace = siqs(&n, None, Some(path), &mut rng, |_| {}).unwrap();
+        assert!(trace.factor.is_some());
+        let resumed = siqs(&n, None, Some(path), &mut rng, |_| {}).unwrap();
+        assert!(resumed.resumed > 0);
+        assert_eq!(resumed.progress.polynomials, 0);
+        assert!(resumed.factor.is_some());
+
+        let other = BigInt::from(1000000016000000063u64);
+        assert!(siqs(&other, None, Some(path), &mut rng, |_| {}).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,9 +1,7 @@
 use num_bigint::{BigInt, BigUint};
 use num_integer::Integer;
-use num_iter::range;
 use num_traits::{One, Zero};
-use num_traits::{Pow, ToPrimitive};
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 pub trait Gcd {
     ///
@@ -92,15 +90,38 @@ pub fn modular_pow(base: &BigInt, e: &BigInt, modulus: &BigInt) -> BigInt {
     a
 }
 
+///
+/// Returns the seed and a reproducible random number generator seeded with it.
+/// If no seed is passed, a fresh seed is drawn so that it can be printed with the
+/// results and the run can be repeated with `--seed`.
+///
+pub fn seeded_rng(seed: Option<u64>) -> (u64, StdRng) {
+    let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+    (seed, StdRng::seed_from_u64(seed))
+}
+
 ///
 /// Generate a random integer in a given range
 ///
-pub fn generate_random_int_in_range(a: &BigInt, b: &BigInt) -> BigInt {
-    let mut rng = rand::thread_rng();
+pub fn generate_random_int_in_range(rng: &mut StdRng, a: &BigInt, b: &BigInt) -> BigInt {
     // return a random BigInt between a and b
     rng.gen_range(a.clone()..b.clone())
 }
 
+///
+/// Returns `rounds` random bases in the range 2 <= a <= n-2 for the probabilistic
+/// primality tests
+///
+pub fn random_bases(rng: &mut StdRng, n: &BigInt, rounds: u8) -> Vec<BigInt> {
+    let (two, n_minus_one) = (BigInt::from(2u64), n - 1);
+    if n_minus_one <= two {
+        return Vec::new();
+    }
+    (0..rounds)
+        .map(|_| generate_random_int_in_range(rng, &two, &n_minus_one))
+        .collect()
+}
+
 pub fn abs_log(x: &BigInt) -> Result<f64, String> {
     use std::cmp::Ordering;
     let zero = BigInt::zero();
@@ -123,52 +144,41 @@ pub fn abs_log(x: &BigInt) -> Result<f64, String> {
 }
 
 ///
-/// Use fast modular exponentiation for polynomials to raise them to a big power.
+/// Raise a polynomial to a big power in the ring (Z/nZ)[x]/(xʳ - 1) by repeated
+/// squaring. Polynomials are coefficient vectors of length r, lowest degree first.
 ///
-pub fn fastpoly(base: &Vec<BigInt>, power: &BigInt, r: &BigInt) -> Vec<BigInt> {
-    let mut base = base.clone();
-    let mut x = Vec::<BigInt>::new();
-    let a = &base[0].clone();
-
-    for _ in 0..base.len() {
-        x.push(BigInt::zero());
-    }
-    x[0] = BigInt::one();
-    let n = power.clone();
+pub fn polynomial_pow_mod(base: &[BigInt], power: &BigInt, n: &BigInt, r: usize) -> Vec<BigInt> {
+    let mut x = vec![BigInt::zero(); r];
+    x[0] = BigInt::one() % n;
+    let mut base = base.to_vec();
     let mut power = power.clone();
 
-    while &power > &BigInt::zero() {
-        if &power % &BigInt::from(2u64) == BigInt::one() {
-            x = polynomial_mul(&x, &base, &n, &r);
+    while power > BigInt::zero() {
+        if power.is_odd() {
+            x = polynomial_mul(&x, &base, n, r);
+        }
+        power >>= 1;
+        if power > BigInt::zero() {
+            base = polynomial_mul(&base, &base, n, r);
         }
-        base = polynomial_mul(&base, &base, &n, &r);
-        power = power / BigInt::from(2u64);
     }
-    x[0] = &x[0] - a;
-    let index = &n % r;
-    let index = index.to_usize().unwrap_or(usize::MAX);
-    x[index] = &x[index] - BigInt::one();
     x
 }
 
-///Function used by fastPoly to multiply two polynomials together.
-pub fn polynomial_mul(a: &Vec<BigInt>, b: &Vec<BigInt>, n: &BigInt, r: &BigInt) -> Vec<BigInt> {
-    let mut x = Vec::<BigInt>::new();
-    for _ in 0..a.len() + b.len() - 1 {
-        x.push(BigInt::zero());
-    }
-    for i in 0..a.len() {
-        for j in 0..b.len() {
-            // usize::MAX is not ideal here. I couldn't find a way to index the vec
-            let index = (i + j) % r.to_usize().unwrap_or(usize::MAX);
-            x[index] += a[i].clone() * b[j].clone();
-            x[index] = &x[index] % n.clone();
+///
+/// Multiply two polynomials of length r in (Z/nZ)[x]/(xʳ - 1): the exponents wrap
+/// around mod r and the coefficients are reduced mod n.
+///
+pub fn polynomial_mul(a: &[BigInt], b: &[BigInt], n: &BigInt, r: usize) -> Vec<BigInt> {
+    let mut x = vec![BigInt::zero(); r];
+    for (i, a_i) in a.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+        for (j, b_j) in b.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+            x[(i + j) % r] += a_i * b_j;
         }
     }
-    for _ in range(r.clone(), BigInt::from(x.len())) {
-        x.pop();
-    }
-    return x;
+    // reduce once per coefficient rather than after every product
+    x.iter_mut().for_each(|c| *c = c.mod_floor(n));
+    x
 }
 
 pub fn modular_inv(n: &BigInt, p: &BigInt) -> BigInt {
@@ -198,29 +208,102 @@ pub fn modular_inv(n: &BigInt, p: &BigInt) -> BigInt {
 }
 
 ///
-/// Find smallest r such that the order of n mod r > ln(n)^2.
+/// Jacobi Symbol (a/n) for an odd positive integer n
 ///
-pub fn findr(n: &BigInt) -> BigInt {
-    let (zero, one) = (BigInt::zero(), BigInt::one());
-    let mut r = BigInt::from(1u64);
-
-    let s: f64 = abs_log(n).unwrap().pow(2);
-    let s = BigInt::from(s.floor() as u64);
-    let mut nex_r = true;
-
-    while nex_r {
-        r += 1;
-        nex_r = false;
-        let mut k = BigInt::zero();
-        while &k <= &s && nex_r == false {
-            k += 1;
-            if modular_pow(n, &k, &r) == zero || modular_pow(n, &k, &r) == one {
-                nex_r = true;
+/// Returns 0 if gcd(a, n) > 1, else ±1. When n is an odd prime this is the Legendre
+/// symbol. Computed using the law of quadratic reciprocity without factorising n:
+/// 1. (a/n) = (a mod n / n)
+/// 2. (2/n) = 1 if n ≡ ±1 (mod 8), -1 if n ≡ ±3 (mod 8)
+/// 3. (a/n) = (n/a) unless a ≡ n ≡ 3 (mod 4), in which case (a/n) = -(n/a)
+///
+pub fn jacobi_symbol(a: &BigInt, n: &BigInt) -> i8 {
+    assert!(
+        n > &BigInt::zero() && n.is_odd(),
+        "Jacobi symbol is defined only for odd positive n"
+    );
+    let (three, four, five, eight) = (
+        BigInt::from(3u64),
+        BigInt::from(4u64),
+        BigInt::from(5u64),
+        BigInt::from(8u64),
+    );
+    let mut a = a.mod_floor(n);
+    let mut n = n.clone();
+    let mut result: i8 = 1;
+
+    while !a.is_zero() {
+        // pull out the factors of 2 from a
+        while a.is_even() {
+            a /= 2;
+            let r = n.mod_floor(&eight);
+            if r == three || r == five {
+                result = -result;
             }
         }
+        // quadratic reciprocity
+        std::mem::swap(&mut a, &mut n);
+        if a.mod_floor(&four) == three && n.mod_floor(&four) == three {
+            result = -result;
+        }
+        a = a.mod_floor(&n);
     }
 
-    r
+    if n.is_one() {
+        result
+    } else {
+        0
+    }
+}
+
+///
+/// Square root of a modulo an odd prime p by the Tonelli-Shanks algorithm
+///
+/// Returns x with x² ≡ a (mod p), None when a is a quadratic non-residue. With
+/// p - 1 = q.2ˢ, q odd, x = a⁽q⁺¹⁾ᐟ² is corrected by powers of c = zq for a
+/// non-residue z until t = aq lands in the trivial subgroup {1}:
+/// 1. i is the least exponent with t^(2ⁱ) ≡ 1 (mod p), i < m
+/// 2. b = c^(2^(m - i - 1)), x = x.b, c = b², t = t.c, m = i
+///
+pub fn sqrt_mod_prime(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let a = a.mod_floor(p);
+    if a.is_zero() || p == &BigInt::from(2u64) {
+        return Some(a);
+    }
+    if jacobi_symbol(&a, p) != 1 {
+        return None;
+    }
+
+    let (mut q, mut s) = (p - 1u32, 0u32);
+    while q.is_even() {
+        q >>= 1;
+        s += 1;
+    }
+    if s == 1 {
+        // p ≡ 3 (mod 4)
+        return Some(modular_pow(&a, &((p + 1u32) >> 2), p));
+    }
+
+    let mut z = BigInt::from(2u64);
+    while jacobi_symbol(&z, p) != -1 {
+        z += 1u32;
+    }
+    let mut m = s;
+    let mut c = modular_pow(&z, &q, p);
+    let mut t = modular_pow(&a, &q, p);
+    let mut x = modular_pow(&a, &((&q + 1u32) >> 1), p);
+    while !t.is_one() {
+        let (mut i, mut t_2i) = (0u32, t.clone());
+        while !t_2i.is_one() {
+            t_2i = &t_2i * &t_2i % p;
+            i += 1;
+        }
+        let b = modular_pow(&c, &(BigInt::one() << (m - i - 1)), p);
+        m = i;
+        c = &b * &b % p;
+        t = t * &c % p;
+        x = x * b % p;
+    }
+    Some(x)
 }
 
 #[cfg(test)]
@@ -259,6 +342,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_jacobi_symbol() {
+        assert_eq!(
+            jacobi_symbol(&BigInt::from(1001u64), &BigInt::from(9907u64)),
+            -1
+        );
+        assert_eq!(jacobi_symbol(&BigInt::from(19u64), &BigInt::from(45u64)), 1);
+        assert_eq!(jacobi_symbol(&BigInt::from(8u64), &BigInt::from(21u64)), -1);
+        assert_eq!(jacobi_symbol(&BigInt::from(5u64), &BigInt::from(21u64)), 1);
+        assert_eq!(jacobi_symbol(&BigInt::from(15u64), &BigInt::from(21u64)), 0);
+        assert_eq!(jacobi_symbol(&BigInt::from(-1i64), &BigInt::from(7u64)), -1);
+    }
+
+    #[test]
+    fn test_sqrt_mod_prime() {
+        // p ≡ 1 (mod 8) takes the full Tonelli-Shanks loop
+        for p in [5u64, 7, 13, 17, 41, 97, 1000000009, 998244353] {
+            let p = BigInt::from(p);
+            for a in [2u64, 3, 5, 10, 12345] {
+                let a = BigInt::from(a);
+                match sqrt_mod_prime(&a, &p) {
+                    Some(x) => assert_eq!((&x * &x) % &p, &a % &p),
+                    None => assert_eq!(jacobi_symbol(&a, &p), -1),
+                }
+            }
+        }
+        assert_eq!(
+            sqrt_mod_prime(&BigInt::from(3u64), &BigInt::from(7u64)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_seeded_rng() {
+        let (seed, mut rng1) = seeded_rng(Some(42));
+        assert_eq!(seed, 42);
+        let (_, mut rng2) = seeded_rng(Some(42));
+        let n = BigInt::from(1000003u64);
+        assert_eq!(
+            random_bases(&mut rng1, &n, 5),
+            random_bases(&mut rng2, &n, 5)
+        );
+    }
+
+    #[test]
+    fn test_polynomial_pow_mod() {
+        // (x + 1)⁵ = x⁵ + 5x⁴ + 10x³ + 10x² + 5x + 1 ≡ 11x² + 10x + 11 ≡ 4x² + 3x + 4
+        // in (Z/7Z)[x]/(x³ - 1)
+        let n = BigInt::from(7u64);
+        let base = vec![BigInt::one(), BigInt::one(), BigInt::zero()];
+        let result = polynomial_pow_mod(&base, &BigInt::from(5u64), &n, 3);
+        assert_eq!(
+            result,
+            vec![BigInt::from(4u64), BigInt::from(3u64), BigInt::from(4u64)]
+        );
+    }
+
     #[test]
     fn test_abs_log() {
         assert_eq!(2.995732273553991, abs_log(&BigInt::from(20u64)).unwrap());